@@ -0,0 +1,124 @@
+//! Runs Kruskal's algorithm on large random graphs with each union-find in `fundamentals::uf`
+//! and reports how long the union-find phase takes, after the edges have been sorted once.
+//! `graph::kruskal_mst::KruskalMST` itself is timed as well, it uses the path-compressing union-find.
+//!
+//! Only std timing is used, so the benchmark runs offline:
+//!
+//! ```text
+//! cargo bench --bench kruskal -- --v 1000000 --e 4000000 --rounds 3
+//! ```
+//!
+//! Options:
+//! - `--v V`          number of vertices (default 1000000)
+//! - `--e E`          number of random edges, on top of a random spanning path so the graph is connected (default 4000000)
+//! - `--rounds R`     rounds per union-find, the best round is reported (default 3)
+//! - `--seed X`       seed of the random generator (default 42)
+//!
+//! The random spanning path is what makes the trees deep: without path compression every `find`
+//! walks up to *O(log V)* links, with it the walks stay a few links long.
+use std::{hint::black_box, time::Instant};
+
+use itualgs_rs::{
+    fundamentals::uf::{WeightedQuickUnionPathCompressionUF, WeightedQuickUnionUF},
+    graph::{edge::Edge, edge_weighted_graph::EdgeWeightedGraph, kruskal_mst::KruskalMST},
+    randomization::lcg_random::Lcg,
+};
+
+/// The operations Kruskal needs from a union-find
+trait UnionFind {
+    fn find(&mut self, p: usize) -> usize;
+    fn union(&mut self, p: usize, q: usize);
+}
+
+impl UnionFind for WeightedQuickUnionUF {
+    fn find(&mut self, p: usize) -> usize { WeightedQuickUnionUF::find(self, p) }
+    fn union(&mut self, p: usize, q: usize) { WeightedQuickUnionUF::union(self, p, q) }
+}
+
+impl UnionFind for WeightedQuickUnionPathCompressionUF {
+    fn find(&mut self, p: usize) -> usize { WeightedQuickUnionPathCompressionUF::find(self, p) }
+    fn union(&mut self, p: usize, q: usize) { WeightedQuickUnionPathCompressionUF::union(self, p, q) }
+}
+
+/// Random edges `(v, w, weight)` over `v` vertices, including a random spanning path
+fn random_edges(v: usize, e: usize, rng: &mut Lcg) -> Vec<(usize, usize, u128)> {
+    let mut order: Vec<usize> = (0..v).collect();
+    rng.shuffle(&mut order);
+    let mut edges: Vec<(usize, usize, u128)> = order
+        .windows(2)
+        .map(|p| (p[0], p[1], rng.uniform(1_000_000) as u128))
+        .collect();
+    for _ in 0..e {
+        edges.push((rng.uniform(v), rng.uniform(v), rng.uniform(1_000_000) as u128));
+    }
+    edges
+}
+
+/// Kruskal over edges sorted by weight, returns the weight of the minimum spanning forest
+fn kruskal(uf: &mut impl UnionFind, v: usize, edges: &[(usize, usize, u128)]) -> u128 {
+    let mut weight = 0;
+    let mut taken = 0;
+    for &(p, q, w) in edges {
+        if taken + 1 >= v {
+            break;
+        }
+        if uf.find(p) != uf.find(q) {
+            uf.union(p, q);
+            weight += w;
+            taken += 1;
+        }
+    }
+    weight
+}
+
+/// Runs Kruskal on a fresh union-find `rounds` times and returns the best time in milliseconds and the weight
+fn best<U: UnionFind>(rounds: u32, v: usize, edges: &[(usize, usize, u128)], new: impl Fn(usize) -> U) -> (f64, u128) {
+    let mut best = f64::MAX;
+    let mut weight = 0;
+    for _ in 0..rounds {
+        let mut uf = new(v);
+        let start = Instant::now();
+        weight = black_box(kruskal(&mut uf, v, edges));
+        best = best.min(start.elapsed().as_secs_f64() * 1000.0);
+    }
+    (best, weight)
+}
+
+fn main() {
+    let mut v: usize = 1_000_000;
+    let mut e: usize = 4_000_000;
+    let mut rounds: u32 = 3;
+    let mut seed: u64 = 42;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--v" => v = args.next().expect("--v needs a value").parse().unwrap(),
+            "--e" => e = args.next().expect("--e needs a value").parse().unwrap(),
+            "--rounds" => rounds = args.next().expect("--rounds needs a value").parse().unwrap(),
+            "--seed" => seed = args.next().expect("--seed needs a value").parse().unwrap(),
+            // cargo bench passes --bench to every bench target
+            "--bench" => (),
+            other => panic!("unknown option {}", other),
+        }
+    }
+
+    let mut rng = Lcg::new(seed);
+    let mut edges = random_edges(v, e, &mut rng);
+    edges.sort_by_key(|edge| edge.2);
+
+    println!("V = {}, E = {}, best of {} rounds", v, edges.len(), rounds);
+    let (weighted, w1) = best(rounds, v, &edges, WeightedQuickUnionUF::new);
+    let (compressed, w2) = best(rounds, v, &edges, WeightedQuickUnionPathCompressionUF::new);
+    assert_eq!(w1, w2, "both union-finds must find the same spanning tree weight");
+    println!("{:<36} {:>10.1} ms", "WeightedQuickUnionUF", weighted);
+    println!("{:<36} {:>10.1} ms {:>8.2}x", "WeightedQuickUnionPathCompressionUF", compressed, weighted / compressed);
+
+    let mut g = EdgeWeightedGraph::new(v);
+    for &(p, q, w) in &edges {
+        g.add_edge(Edge::new(p, q, w));
+    }
+    let start = Instant::now();
+    let mst = KruskalMST::new(g);
+    println!("{:<36} {:>10.1} ms (including the sort)", "KruskalMST::new", start.elapsed().as_secs_f64() * 1000.0);
+    assert_eq!(mst.weight, w1);
+}
@@ -0,0 +1,116 @@
+//! Compares the linked-list `fundamentals::queue::Queue` with the circular buffer
+//! `fundamentals::resizing_array_queue::ResizingArrayQueue`, using std's `VecDeque` as a reference.
+//!
+//! Only std timing is used, so the benchmark runs offline:
+//!
+//! ```text
+//! cargo bench --bench queue -- --n 1000000 --rounds 5
+//! ```
+//!
+//! Options:
+//! - `--n N`        number of items per round (default 1000000)
+//! - `--rounds R`   rounds per workload, the best round is reported (default 5)
+//!
+//! Workloads:
+//! - `fill-drain`   enqueue N items, then dequeue all of them
+//! - `steady`       keep 1000 items queued while N items pass through
+//! - `sized`        like `fill-drain`, but the queue is created with room for N items up front
+use std::{collections::VecDeque, hint::black_box, time::Instant};
+
+use itualgs_rs::fundamentals::{queue::Queue, resizing_array_queue::ResizingArrayQueue};
+
+/// The operations every benchmarked queue supports
+trait Fifo {
+    fn enqueue(&mut self, val: u64);
+    fn dequeue(&mut self) -> Option<u64>;
+}
+
+impl Fifo for Queue<u64> {
+    fn enqueue(&mut self, val: u64) { Queue::enqueue(self, val) }
+    fn dequeue(&mut self) -> Option<u64> { Queue::dequeue(self) }
+}
+
+impl Fifo for ResizingArrayQueue<u64> {
+    fn enqueue(&mut self, val: u64) { ResizingArrayQueue::enqueue(self, val) }
+    fn dequeue(&mut self) -> Option<u64> { ResizingArrayQueue::dequeue(self) }
+}
+
+impl Fifo for VecDeque<u64> {
+    fn enqueue(&mut self, val: u64) { self.push_back(val) }
+    fn dequeue(&mut self) -> Option<u64> { self.pop_front() }
+}
+
+fn fill_drain(q: &mut impl Fifo, n: u64) -> u64 {
+    for i in 0..n {
+        q.enqueue(i);
+    }
+    let mut sum = 0;
+    while let Some(v) = q.dequeue() {
+        sum += v;
+    }
+    sum
+}
+
+fn steady(q: &mut impl Fifo, n: u64) -> u64 {
+    for i in 0..1000 {
+        q.enqueue(i);
+    }
+    let mut sum = 0;
+    for i in 0..n {
+        q.enqueue(i);
+        sum += q.dequeue().unwrap();
+    }
+    sum
+}
+
+/// Runs `f` on a fresh queue `rounds` times and returns the best time in nanoseconds per item
+fn best<Q: Fifo>(rounds: u32, n: u64, new: impl Fn() -> Q, f: impl Fn(&mut Q, u64) -> u64) -> f64 {
+    let mut best = f64::MAX;
+    for _ in 0..rounds {
+        let mut q = new();
+        let start = Instant::now();
+        black_box(f(&mut q, n));
+        best = best.min(start.elapsed().as_nanos() as f64 / n as f64);
+    }
+    best
+}
+
+fn report(workload: &str, linked: f64, resizing: f64, deque: f64) {
+    println!("{:<12} {:>12.2} {:>12.2} {:>12.2} {:>9.2}x", workload, linked, resizing, deque, linked / resizing);
+}
+
+fn main() {
+    let mut n: u64 = 1_000_000;
+    let mut rounds: u32 = 5;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--n" => n = args.next().expect("--n needs a value").parse().unwrap(),
+            "--rounds" => rounds = args.next().expect("--rounds needs a value").parse().unwrap(),
+            // cargo bench passes --bench to every bench target
+            "--bench" => (),
+            other => panic!("unknown option {}", other),
+        }
+    }
+
+    println!("n = {}, best of {} rounds, ns per item", n, rounds);
+    println!("{:<12} {:>12} {:>12} {:>12} {:>10}", "workload", "Queue", "Resizing", "VecDeque", "speedup");
+    report(
+        "fill-drain",
+        best(rounds, n, Queue::new, fill_drain),
+        best(rounds, n, ResizingArrayQueue::new, fill_drain),
+        best(rounds, n, VecDeque::new, fill_drain),
+    );
+    report(
+        "steady",
+        best(rounds, n, Queue::new, steady),
+        best(rounds, n, ResizingArrayQueue::new, steady),
+        best(rounds, n, VecDeque::new, steady),
+    );
+    report(
+        "sized",
+        best(rounds, n, Queue::new, fill_drain),
+        best(rounds, n, || ResizingArrayQueue::with_capacity(n as usize), fill_drain),
+        best(rounds, n, || VecDeque::with_capacity(n as usize), fill_drain),
+    );
+}
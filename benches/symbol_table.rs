@@ -0,0 +1,270 @@
+//! Replays symbol-table workloads against `searching::BST::BST`, `BTreeMap` and `HashMap`
+//! and reports operations per second, key comparisons per operation and the height of the BST.
+//!
+//! Only std timing is used, so the benchmark runs offline:
+//!
+//! ```text
+//! cargo bench --bench symbol_table -- --n 20000 --ops 200000 --workload zipf --read-ratio 0.9
+//! ```
+//!
+//! Options:
+//! - `--n N`             size of the key space `0..N` and number of puts in the load phase (default 20000)
+//! - `--ops M`           number of operations in the mixed phase (default 200000)
+//! - `--workload W`      `random`, `sorted`, `zipf` or `all` (default `all`)
+//! - `--read-ratio R`    fraction of gets in the mixed phase, repeatable (default 0.5, 0.9 and 0.99)
+//! - `--zipf-s S`        exponent of the Zipf distribution (default 1.0)
+//! - `--seed X`          seed of the random generator (default 42)
+//!
+//! The `sorted` workload loads the keys in ascending order, which degenerates the unbalanced BST into a list.
+//! Keep `--n` moderate for it, since every operation then costs *O(N)*.
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    hash::{Hash, Hasher},
+    time::Instant,
+};
+
+use itualgs_rs::{randomization::lcg_random::Lcg, searching::BST::BST};
+
+thread_local! {
+    static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn count() {
+    COMPARISONS.with(|c| c.set(c.get() + 1));
+}
+
+fn take_comparisons() -> u64 {
+    COMPARISONS.with(|c| c.replace(0))
+}
+
+/// A key that counts every comparison and equality check made on it
+#[derive(Clone, Copy, Debug)]
+struct Key(u64);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        count();
+        self.0 == other.0
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        count();
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Workload {
+    Random,
+    Sorted,
+    Zipf,
+}
+
+impl Workload {
+    fn name(&self) -> &'static str {
+        match self {
+            Workload::Random => "random",
+            Workload::Sorted => "sorted",
+            Workload::Zipf => "zipf",
+        }
+    }
+}
+
+struct Config {
+    n: u64,
+    ops: u64,
+    workloads: Vec<Workload>,
+    read_ratios: Vec<f64>,
+    zipf_s: f64,
+    seed: u64,
+}
+
+fn parse_args() -> Config {
+    let mut config = Config {
+        n: 20_000,
+        ops: 200_000,
+        workloads: vec![Workload::Random, Workload::Sorted, Workload::Zipf],
+        read_ratios: Vec::new(),
+        zipf_s: 1.0,
+        seed: 42,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{} needs a value", arg));
+        match arg.as_str() {
+            "--n" => config.n = value().parse().unwrap(),
+            "--ops" => config.ops = value().parse().unwrap(),
+            "--read-ratio" => config.read_ratios.push(value().parse().unwrap()),
+            "--zipf-s" => config.zipf_s = value().parse().unwrap(),
+            "--seed" => config.seed = value().parse().unwrap(),
+            "--workload" => {
+                config.workloads = match value().as_str() {
+                    "random" => vec![Workload::Random],
+                    "sorted" => vec![Workload::Sorted],
+                    "zipf" => vec![Workload::Zipf],
+                    "all" => vec![Workload::Random, Workload::Sorted, Workload::Zipf],
+                    other => panic!("unknown workload {}", other),
+                }
+            },
+            // cargo bench passes --bench to every bench target
+            "--bench" => (),
+            other => panic!("unknown option {}", other),
+        }
+    }
+    if config.read_ratios.is_empty() {
+        config.read_ratios = vec![0.5, 0.9, 0.99];
+    }
+    config
+}
+
+/// Draws keys from `0..n` according to a workload
+struct KeySource {
+    workload: Workload,
+    rng: Lcg,
+    n: u64,
+    next_sorted: u64,
+    // Cumulative Zipf probabilities of the ranks 1..=n
+    cdf: Vec<f64>,
+}
+
+impl KeySource {
+    fn new(workload: Workload, n: u64, zipf_s: f64, seed: u64) -> Self {
+        let mut cdf = Vec::new();
+        if workload == Workload::Zipf {
+            let mut total = 0.0;
+            for rank in 1..=n {
+                total += 1.0 / (rank as f64).powf(zipf_s);
+                cdf.push(total);
+            }
+            for p in cdf.iter_mut() {
+                *p /= total;
+            }
+        }
+        KeySource { workload, rng: Lcg::new(seed), n, next_sorted: 0, cdf }
+    }
+
+    fn next(&mut self) -> Key {
+        let k = match self.workload {
+            Workload::Random => self.rng.uniform(self.n as usize) as u64,
+            Workload::Sorted => {
+                let k = self.next_sorted % self.n;
+                self.next_sorted += 1;
+                k
+            },
+            Workload::Zipf => {
+                let u = self.rng.next_f64();
+                let rank = self.cdf.partition_point(|p| *p < u) as u64;
+                // Scatter the ranks so the popular keys are not all at one end of the key space
+                rank.wrapping_mul(2654435761) % self.n
+            },
+        };
+        Key(k)
+    }
+}
+
+/// The operations every benchmarked symbol table supports
+trait SymbolTable {
+    fn name(&self) -> &'static str;
+    fn put(&mut self, key: Key, val: u64);
+    fn get(&self, key: &Key) -> Option<u64>;
+    fn height(&self) -> Option<usize>;
+}
+
+impl SymbolTable for BST<Key,u64> {
+    fn name(&self) -> &'static str { "BST" }
+    fn put(&mut self, key: Key, val: u64) { BST::put(self, key, val) }
+    fn get(&self, key: &Key) -> Option<u64> { BST::get(self, key).copied() }
+    fn height(&self) -> Option<usize> { Some(BST::height(self)) }
+}
+
+impl SymbolTable for BTreeMap<Key,u64> {
+    fn name(&self) -> &'static str { "BTreeMap" }
+    fn put(&mut self, key: Key, val: u64) { self.insert(key, val); }
+    fn get(&self, key: &Key) -> Option<u64> { BTreeMap::get(self, key).copied() }
+    fn height(&self) -> Option<usize> { None }
+}
+
+impl SymbolTable for HashMap<Key,u64> {
+    fn name(&self) -> &'static str { "HashMap" }
+    fn put(&mut self, key: Key, val: u64) { self.insert(key, val); }
+    fn get(&self, key: &Key) -> Option<u64> { HashMap::get(self, key).copied() }
+    fn height(&self) -> Option<usize> { None }
+}
+
+struct Measurement {
+    ops_per_sec: f64,
+    cmp_per_op: f64,
+}
+
+fn measure(ops: u64, mut f: impl FnMut()) -> Measurement {
+    take_comparisons();
+    let start = Instant::now();
+    for _ in 0..ops {
+        f();
+    }
+    let secs = start.elapsed().as_secs_f64().max(1e-9);
+    let comparisons = take_comparisons();
+    Measurement {
+        ops_per_sec: ops as f64 / secs,
+        cmp_per_op: comparisons as f64 / ops.max(1) as f64,
+    }
+}
+
+fn report(table: &str, phase: &str, m: &Measurement, height: Option<usize>) {
+    let height = height.map_or("-".to_string(), |h| h.to_string());
+    println!("{:<10} {:<16} {:>14.0} {:>10.2} {:>8}", table, phase, m.ops_per_sec, m.cmp_per_op, height);
+}
+
+fn run(table: &mut dyn SymbolTable, workload: Workload, config: &Config) {
+    let mut keys = KeySource::new(workload, config.n, config.zipf_s, config.seed);
+    let load = measure(config.n, || {
+        let k = keys.next();
+        table.put(k, k.0);
+    });
+    report(table.name(), "load", &load, table.height());
+
+    for (i, ratio) in config.read_ratios.iter().enumerate() {
+        let mut rng = Lcg::new(config.seed + 1 + i as u64);
+        let mut sink = 0;
+        let mixed = measure(config.ops, || {
+            let k = keys.next();
+            if rng.next_f64() < *ratio {
+                sink += table.get(&k).unwrap_or(0);
+            } else {
+                table.put(k, k.0);
+            }
+        });
+        std::hint::black_box(sink);
+        report(table.name(), &format!("{:.0}% reads", ratio * 100.0), &mixed, table.height());
+    }
+}
+
+fn main() {
+    let config = parse_args();
+    for workload in &config.workloads {
+        println!();
+        println!("workload: {}, n = {}, ops = {}", workload.name(), config.n, config.ops);
+        println!("{:<10} {:<16} {:>14} {:>10} {:>8}", "table", "phase", "ops/sec", "cmp/op", "height");
+        run(&mut BST::<Key,u64>::new(), *workload, &config);
+        run(&mut BTreeMap::<Key,u64>::new(), *workload, &config);
+        run(&mut HashMap::<Key,u64>::new(), *workload, &config);
+    }
+}
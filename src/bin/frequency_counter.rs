@@ -0,0 +1,198 @@
+//! Counts how often every word occurs in a text and prints the most frequent ones,
+//! after `FrequencyCounter` in Section 3.1 of Algorithms, 4th Edition.
+//!
+//! The counts are kept in a `searching::BST::BST<String, usize>`.
+//!
+//! ```text
+//! Usage: frequency-counter [--top K] [--min-len N] [--csv] [FILE]
+//!
+//!   --top K       print the K most frequent words (default 10)
+//!   --min-len N   ignore words shorter than N characters (default 1)
+//!   --csv         print `word,count` rows with a header instead of aligned columns
+//!   FILE          the text to read, standard input if left out or `-`
+//! ```
+//!
+//! Words are the runs of letters, digits and inner apostrophes, compared case-insensitively.
+//! Ties in the count are printed in alphabetical order.
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, Read, Write},
+    process,
+};
+
+use itualgs_rs::searching::BST::BST;
+
+const USAGE: &str = "Usage: frequency-counter [--top K] [--min-len N] [--csv] [FILE]";
+
+struct Options {
+    top: usize,
+    min_len: usize,
+    csv: bool,
+    help: bool,
+    path: Option<String>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options { top: 10, min_len: 1, csv: false, help: false, path: None };
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" | "--min-len" => {
+                let value = args.next().ok_or(format!("{} needs a value", arg))?;
+                let value: usize = value.parse().map_err(|_| format!("{} is not a number: {}", arg, value))?;
+                if arg == "--top" {
+                    options.top = value;
+                } else {
+                    options.min_len = value;
+                }
+            },
+            "--csv" => options.csv = true,
+            "-h" | "--help" => {
+                options.help = true;
+                return Ok(options);
+            },
+            "-" => options.path = None,
+            other if other.starts_with("--") => return Err(format!("unknown option {}", other)),
+            other => options.path = Some(other.to_string()),
+        }
+    }
+    Ok(options)
+}
+
+/// Splits `text` into lowercase words of at least `min_len` characters
+fn words(text: &str, min_len: usize) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|w| w.trim_matches('\''))
+        .filter(move |w| !w.is_empty() && w.chars().count() >= min_len)
+        .map(|w| w.to_lowercase())
+}
+
+/// Counts every word of `text` in a symbol table
+fn count_words(text: &str, min_len: usize) -> BST<String, usize> {
+    let mut st: BST<String, usize> = BST::new();
+    for word in words(text, min_len) {
+        match st.get_mut(&word) {
+            Some(count) => *count += 1,
+            None => st.put(word, 1),
+        }
+    }
+    st
+}
+
+/// Returns the `k` most frequent words, ties broken alphabetically
+fn top_k(st: &BST<String, usize>, k: usize) -> Vec<(&String, usize)> {
+    let mut counts: Vec<(&String, usize)> = Vec::with_capacity(st.size());
+    if let (Some(lo), Some(hi)) = (st.min(), st.max()) {
+        let mut all = st.range(lo, hi);
+        while let Some((word, count)) = all.dequeue() {
+            counts.push((word, *count));
+        }
+    }
+    // The range comes out alphabetically, so a stable sort keeps ties in that order
+    counts.sort_by_key(|c| Reverse(c.1));
+    counts.truncate(k);
+    counts
+}
+
+fn write_report(out: &mut impl Write, top: &[(&String, usize)], csv: bool) -> io::Result<()> {
+    if csv {
+        writeln!(out, "word,count")?;
+        for (word, count) in top {
+            writeln!(out, "{},{}", word, count)?;
+        }
+    } else {
+        let width = top.iter().map(|(w, _)| w.chars().count()).max().unwrap_or(0);
+        for (word, count) in top {
+            writeln!(out, "{:<width$} {}", word, count, width = width)?;
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let text = match &options.path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map(|_| text)
+        },
+    };
+    let text = text.unwrap_or_else(|e| {
+        eprintln!("frequency-counter: {}", e);
+        process::exit(1);
+    });
+
+    let st = count_words(&text, options.min_len);
+    let top = top_k(&st, options.top);
+    if let Err(e) = write_report(&mut io::stdout().lock(), &top, options.csv) {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("frequency-counter: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_words, parse_args, top_k, write_report};
+
+    const TALE: &str = "It was the best of times, it was the worst of times, \
+        it was the age of wisdom, it was the age of foolishness, \
+        it was the epoch of belief, it was the epoch of incredulity...";
+
+    #[test]
+    fn test_tale_of_two_cities() {
+        let st = count_words(TALE, 1);
+        assert_eq!(st.get(&"it".to_string()), Some(&6));
+        assert_eq!(st.get(&"of".to_string()), Some(&6));
+        assert_eq!(st.get(&"times".to_string()), Some(&2));
+        assert_eq!(st.size(), 13);
+
+        let top = top_k(&st, 4);
+        let words: Vec<(&str, usize)> = top.iter().map(|(w, c)| (w.as_str(), *c)).collect();
+        assert_eq!(words, vec![("it", 6), ("of", 6), ("the", 6), ("was", 6)]);
+    }
+
+    #[test]
+    fn test_min_len_and_csv() {
+        let st = count_words(TALE, 5);
+        let top = top_k(&st, 3);
+        let mut out = Vec::new();
+        write_report(&mut out, &top, true).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "word,count\nepoch,2\ntimes,2\nbelief,1\n");
+    }
+
+    #[test]
+    fn test_apostrophes_and_case() {
+        let st = count_words("Don't stop, DON'T 'quote' me", 1);
+        assert_eq!(st.get(&"don't".to_string()), Some(&2));
+        assert_eq!(st.get(&"quote".to_string()), Some(&1));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = ["--top", "3", "--csv", "tale.txt"].iter().map(|s| s.to_string());
+        let options = parse_args(args).unwrap();
+        assert_eq!(options.top, 3);
+        assert!(options.csv);
+        assert_eq!(options.path.as_deref(), Some("tale.txt"));
+
+        assert!(parse_args(["--top", "x"].iter().map(|s| s.to_string())).is_err());
+        assert!(parse_args(["--bogus"].iter().map(|s| s.to_string())).is_err());
+        assert!(parse_args(["--help", "--bogus"].iter().map(|s| s.to_string())).unwrap().help);
+    }
+}
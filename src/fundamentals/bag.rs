@@ -1,4 +1,6 @@
-use std::collections::{LinkedList, binary_heap::Iter};
+use std::collections::{LinkedList, linked_list};
+
+use super::iter::delegate_iterator;
 
 
 /// A bag is an onorderd data strructure of items which can be iterated over.
@@ -78,13 +80,113 @@ impl<T> Bag<T> where T:Clone {
     }
 }
 
+impl<T> Bag<T> {
+    /// Returns an iterator over the elements in no particular order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { inner: self.elements.iter() }
+    }
+
+    /// Returns an iterator that allows modifying each element in no particular order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { inner: self.elements.iter_mut() }
+    }
+}
+
+delegate_iterator! {
+    /// An iterator over the elements of a `Bag`, created by `Bag::iter`.
+    pub struct Iter<'a, T>(linked_list::Iter<'a, T>) -> &'a T;
+}
+
+delegate_iterator! {
+    /// A mutable iterator over the elements of a `Bag`, created by `Bag::iter_mut`.
+    pub struct IterMut<'a, T>(linked_list::IterMut<'a, T>) -> &'a mut T;
+}
 
+delegate_iterator! {
+    /// An owning iterator over the elements of a `Bag`, created by `into_iter`.
+    pub struct IntoIter<T>(linked_list::IntoIter<T>) -> T;
+}
+
+impl<T> IntoIterator for Bag<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self.elements.into_iter() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Bag<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Bag<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for Bag<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.elements.push_front(item);
+            self.size += 1;
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Bag<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Bag<T> {
+        let mut bag = Bag { elements: LinkedList::new(), size: 0 };
+        bag.extend(iter);
+        bag
+    }
+}
+
+
+
+/// Serializes the bag as a sequence in iteration order
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Bag<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.elements)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Bag<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let elements = LinkedList::deserialize(deserializer)?;
+        let size = elements.len();
+        Ok(Bag { elements, size })
+    }
+}
 
 #[cfg(test)]
 mod tests {
 
     use super::Bag;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let bag = Bag::new_from_vec(&vec!['f','e','r']);
+        let json = serde_json::to_string(&bag).unwrap();
+        assert_eq!(json, r#"["r","e","f"]"#);
+
+        let mut back: Bag<char> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.size(), 3);
+        assert_eq!(back.to_list(), vec!['r','e','f']);
+    }
+
 
     #[test]
     fn test_bag() {
@@ -95,4 +197,19 @@ mod tests {
         to_list.reverse();
         assert_eq!(&to_list,&list);
     }
+
+    #[test]
+    fn test_iter() {
+        let mut bag: Bag<u8> = vec![1, 2].into_iter().collect();
+        bag.extend(vec![3]);
+        assert_eq!(bag.size(), 3);
+
+        for item in &mut bag {
+            *item += 1;
+        }
+        let mut items: Vec<u8> = bag.iter().copied().collect();
+        items.sort();
+        assert_eq!(items, vec![2, 3, 4]);
+        assert_eq!(bag.into_iter().count(), 3);
+    }
 }
@@ -1,10 +1,16 @@
+use std::{cmp::Ordering, ops::Range};
+
 /// The fundamentals binary search represents a generic iterative binary search
 /// it has the index_of method which returns the index of a searched for key.
 /// This has a time complexity of *O*(log(n)) and has the recurrence relation:
 /// T(n) = T(n/2) + 1
-/// 
+///
+/// Every search here is built on `partition_point`, which keeps a half-open range `lo..hi`,
+/// so none of them panic, also not on an empty slice or a key outside the slice.
+/// When a key occurs more than once, `index_of` returns its first index and `equal_range` all of them.
+///
 /// Author: cave
-/// 
+///
 /// # Examples
 /// ```
 /// use itualgs_rs::fundamentals::binary_search;
@@ -12,45 +18,249 @@
 /// let list = vec![0,1,2,3,4,5,6,7,8,9];
 /// let index_of_three = binary_search::index_of(&list, 3);
 /// assert!(index_of_three.unwrap() == 3)
-/// ```  
+/// ```
+///
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::{lower_bound, upper_bound, equal_range, count_of};
+///
+/// let list = vec![1, 3, 3, 3, 8];
+/// assert_eq!(lower_bound(&list, 3), 1);
+/// assert_eq!(upper_bound(&list, 3), 4);
+/// assert_eq!(equal_range(&list, 3), 1..4);
+/// assert_eq!(count_of(&list, 3), 3);
+///
+/// // A missing key gives the index where it would be inserted
+/// assert_eq!(equal_range(&list, 5), 4..4);
+/// assert_eq!(lower_bound(&list, 0), 0);
+/// assert_eq!(upper_bound(&list, 9), 5);
+/// ```
 pub fn index_of<T>(array: &[T], key: T) -> Option<usize>
-where 
+where
     T: PartialOrd,
 {
-    // Get the middle element, high, and low begin here
-    let mut hi = array.len() - 1;
-    let mut lo = 0;
-    let mut middle = array.len() / 2;
+    let lo = lower_bound_ref(array, &key);
+    // lo is the first element that is not smaller than the key, so it is either the key or the key is missing
+    match array.get(lo) {
+        Some(value) if *value == key => Some(lo),
+        _ => None,
+    }
+}
 
-    // Get the value
-    let mut current_value = array.get(middle);
+/// Returns the first index whose element is not smaller than the key,
+/// or the length of the slice if every element is smaller.
+pub fn lower_bound<T: PartialOrd>(array: &[T], key: T) -> usize {
+    lower_bound_ref(array, &key)
+}
 
-    // While we haven't iterated through every element, continue searching
-    while hi >= lo {
-        middle = lo + (hi - lo) / 2;
-        current_value = array.get(middle);
+fn lower_bound_ref<T: PartialOrd>(array: &[T], key: &T) -> usize {
+    partition_point(array, |value| value < key)
+}
+
+/// Returns the first index whose element is greater than the key,
+/// or the length of the slice if no element is greater.
+pub fn upper_bound<T: PartialOrd>(array: &[T], key: T) -> usize {
+    partition_point(array, |value| value <= &key)
+}
+
+/// Returns the range of indices whose elements are equal to the key.
+/// It is empty, and starts where the key would be inserted, if the key is missing.
+pub fn equal_range<T: PartialOrd>(array: &[T], key: T) -> Range<usize> {
+    let lo = lower_bound_ref(array, &key);
+    let hi = lo + partition_point(&array[lo..], |value| value <= &key);
+    lo..hi
+}
+
+/// Returns how many times the key occurs.
+pub fn count_of<T: PartialOrd>(array: &[T], key: T) -> usize {
+    equal_range(array, key).len()
+}
 
-        // our current guess was too high
-        if Some(&key) < current_value {
-            hi = middle - 1;
+/// Returns the first index for which `pred` is false, given a slice where `pred` is true
+/// for some prefix and false for the rest. Returns the length of the slice if `pred` is always true.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::partition_point;
+///
+/// let scores = vec![98, 91, 85, 70, 62, 40];
+/// // Sorted descending, so "at least 70" holds for a prefix
+/// assert_eq!(partition_point(&scores, |s| *s >= 70), 4);
+/// assert_eq!(partition_point(&scores, |_| true), 6);
+/// ```
+pub fn partition_point<T>(array: &[T], mut pred: impl FnMut(&T) -> bool) -> usize {
+    let mut lo = 0;
+    let mut hi = array.len();
+    // pred is true before lo and false from hi on
+    while lo < hi {
+        let middle = lo + (hi - lo) / 2;
+        if pred(&array[middle]) {
+            lo = middle + 1;
+        } else {
+            hi = middle;
         }
-        // our current guess was too low 
-        else if Some(&key) > current_value {
+    }
+    lo
+}
+
+/// Returns the first index for which `cmp` returns `Equal`, given a slice sorted so that `cmp`
+/// returns `Less` for a prefix, then `Equal`, then `Greater`. The comparator tells how an element
+/// compares to the element searched for.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::index_of_by;
+///
+/// let people = vec![("Alice", 31), ("Bob", 27), ("Eve", 31), ("Mallory", 45)];
+/// // Sorted by name, so look a name up without building a whole tuple
+/// assert_eq!(index_of_by(&people, |p| p.0.cmp("Eve")), Some(2));
+/// assert_eq!(index_of_by(&people, |p| p.0.cmp("Trent")), None);
+/// ```
+pub fn index_of_by<T>(array: &[T], mut cmp: impl FnMut(&T) -> Ordering) -> Option<usize> {
+    let lo = partition_point(array, |value| cmp(value) == Ordering::Less);
+    match array.get(lo) {
+        Some(value) if cmp(value) == Ordering::Equal => Some(lo),
+        _ => None,
+    }
+}
+
+/// Returns the smallest integer in `lo..=hi` for which `pred` is true, given that `pred` is false
+/// up to some point and true from there on. Returns None if `pred` is false on the whole range.
+/// This is "binary searching the answer": `pred` checks if a candidate answer is good enough.
+/// Calls `pred` *O*(log(hi - lo)) times and does not overflow, also not for the full range of i64.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::search_integer;
+///
+/// // The smallest truck capacity that ships the parcels, in order, in at most 3 trips
+/// let parcels = [4, 8, 2, 7, 5, 3];
+/// let trips = |capacity: i64| {
+///     let (mut trips, mut load) = (1, 0);
+///     for p in parcels {
+///         if load + p > capacity {
+///             trips += 1;
+///             load = 0;
+///         }
+///         load += p;
+///     }
+///     trips
+/// };
+/// assert_eq!(search_integer(8, 29, |c| trips(c) <= 3), Some(12));
+/// assert_eq!(search_integer(8, 29, |c| trips(c) <= 0), None);
+/// ```
+pub fn search_integer(lo: i64, hi: i64, mut pred: impl FnMut(i64) -> bool) -> Option<i64> {
+    if lo > hi {
+        return None;
+    }
+    // pred is false before lo and true from hi on, or nowhere if hi is still one past the range
+    let (mut lo, mut hi) = (lo as i128, hi as i128 + 1);
+    let end = hi;
+    while lo < hi {
+        let middle = lo + (hi - lo) / 2;
+        if pred(middle as i64) {
+            hi = middle;
+        } else {
             lo = middle + 1;
-        } 
-        // we found the correct value from the key
-        else {
-            return Some(middle);
         }
-        
     }
+    if lo == end { None } else { Some(lo as i64) }
+}
+
+/// Returns the smallest x in `lo..=hi`, to within `eps`, for which `pred` is true, given that `pred` is false
+/// up to some point and true from there on. The answer is always a point where `pred` is true.
+/// Returns None if `pred(hi)` is false. Stops early when the interval cannot be split any further in f64.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::search_real;
+///
+/// let root = search_real(0.0, 2.0, 1e-9, |x| x * x >= 2.0).unwrap();
+/// assert!((root - 2f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn search_real(lo: f64, hi: f64, eps: f64, mut pred: impl FnMut(f64) -> bool) -> Option<f64> {
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    let (mut lo, mut hi) = (lo, hi);
+    while hi - lo > eps {
+        let middle = lo + (hi - lo) / 2.0;
+        if middle <= lo || middle >= hi {
+            break;
+        }
+        if pred(middle) {
+            hi = middle;
+        } else {
+            lo = middle;
+        }
+    }
+    Some(hi)
+}
 
-    return None;
+/// Returns the smallest integer from `lo` on for which `pred` is true, when there is no known upper bound,
+/// given that `pred` is false up to some point and true from there on.
+/// It gallops to `lo`, `lo + 1`, `lo + 3`, `lo + 7`, ... until `pred` is true, then binary searches the last gap,
+/// so it calls `pred` *O*(log d) times where d is the distance from `lo` to the answer.
+/// Returns None if `pred` is still false at `i64::MAX`.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::exponential_search;
+///
+/// // The first power of two above a million, without guessing an upper bound
+/// let exponent = exponential_search(0, |e| 1u64 << e > 1_000_000).unwrap();
+/// assert_eq!(exponent, 20);
+/// ```
+pub fn exponential_search(lo: i64, mut pred: impl FnMut(i64) -> bool) -> Option<i64> {
+    let mut start = lo;
+    let mut step: i64 = 1;
+    let mut probe = lo;
+    loop {
+        if pred(probe) {
+            return search_integer(start, probe, pred);
+        }
+        if probe == i64::MAX {
+            return None;
+        }
+        start = probe + 1;
+        probe = probe.saturating_add(step);
+        step = step.saturating_mul(2);
+    }
+}
+
+/// Returns the x in `lo..=hi`, to within `eps`, where a unimodal function is largest.
+/// The function must increase up to its peak and decrease after it, for a minimum search on `-f` instead.
+/// Each step throws away a third of the interval, so `f` is called *O*(log((hi - lo) / eps)) times.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::binary_search::ternary_search;
+///
+/// let peak = ternary_search(0.0, 10.0, 1e-7, |x| -(x - 3.0) * (x - 3.0) + 4.0);
+/// assert!((peak - 3.0).abs() < 1e-6);
+///
+/// let lowest = ternary_search(-5.0, 5.0, 1e-7, |x| -x.cosh());
+/// assert!(lowest.abs() < 1e-3);
+/// ```
+pub fn ternary_search(lo: f64, hi: f64, eps: f64, mut f: impl FnMut(f64) -> f64) -> f64 {
+    let (mut lo, mut hi) = (lo, hi);
+    while hi - lo > eps {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if m1 <= lo || m2 >= hi {
+            break;
+        }
+        if f(m1) < f(m2) {
+            lo = m1;
+        } else {
+            hi = m2;
+        }
+    }
+    lo + (hi - lo) / 2.0
 }
 
 #[cfg(test)]
 mod tests {
-    use super::index_of;
+    use super::{count_of, equal_range, exponential_search, index_of, index_of_by, lower_bound, partition_point, search_integer, search_real, ternary_search, upper_bound};
     #[test]
     fn test_search_last_index() {
         let list = vec![0,3,5,7,9,10,23,31,32,94];
@@ -78,4 +288,121 @@ mod tests {
         let var = index_of(&list, "abcdefgh");
         assert!(var.unwrap() == 5)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_empty_and_out_of_range() {
+        let empty: Vec<i32> = Vec::new();
+        assert_eq!(index_of(&empty, 1), None);
+        assert_eq!(lower_bound(&empty, 1), 0);
+        assert_eq!(upper_bound(&empty, 1), 0);
+        assert_eq!(equal_range(&empty, 1), 0..0);
+        assert_eq!(count_of(&empty, 1), 0);
+        assert_eq!(index_of_by(&empty, |v| v.cmp(&1)), None);
+
+        let list = vec![5, 6, 7];
+        assert_eq!(index_of(&list, 4), None);
+        assert_eq!(index_of(&list, 8), None);
+        assert_eq!(index_of(&[u32::MIN], 0), Some(0));
+        assert_eq!(index_of(&[1.5, f64::NAN], 2.0), None);
+    }
+
+    /// Every non-decreasing list of the given length over the values `0..3`
+    fn sorted_lists(len: usize) -> Vec<Vec<i32>> {
+        if len == 0 {
+            return vec![Vec::new()];
+        }
+        let mut lists = Vec::new();
+        for mut list in sorted_lists(len - 1) {
+            let last = list.last().copied().unwrap_or(0);
+            for v in last..3 {
+                list.push(v);
+                lists.push(list.clone());
+                list.pop();
+            }
+        }
+        lists
+    }
+
+    #[test]
+    fn test_exhaustive_against_linear_scan() {
+        for len in 0..=8 {
+            for list in sorted_lists(len) {
+                for key in -1..=3 {
+                    let lo = list.iter().filter(|v| **v < key).count();
+                    let hi = list.iter().filter(|v| **v <= key).count();
+                    let first = list.iter().position(|v| *v == key);
+
+                    assert_eq!(lower_bound(&list, key), lo, "{:?} {}", list, key);
+                    assert_eq!(upper_bound(&list, key), hi, "{:?} {}", list, key);
+                    assert_eq!(equal_range(&list, key), lo..hi, "{:?} {}", list, key);
+                    assert_eq!(count_of(&list, key), hi - lo, "{:?} {}", list, key);
+                    assert_eq!(index_of(&list, key), first, "{:?} {}", list, key);
+                    assert_eq!(index_of_by(&list, |v| v.cmp(&key)), first, "{:?} {}", list, key);
+                }
+                for split in 0..=len {
+                    let flags: Vec<bool> = (0..len).map(|i| i < split).collect();
+                    assert_eq!(partition_point(&flags, |f| *f), split);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicate_heavy() {
+        let mut list = vec![7; 1000];
+        list.insert(0, 1);
+        list.push(9);
+        assert_eq!(index_of(&list, 7), Some(1));
+        assert_eq!(equal_range(&list, 7), 1..1001);
+        assert_eq!(count_of(&list, 7), 1000);
+        assert_eq!(count_of(&list, 9), 1);
+        assert_eq!(equal_range(&list, 8), 1001..1001);
+    }
+
+    #[test]
+    fn test_search_integer_bounds() {
+        for answer in -3..=3 {
+            let expected = if answer > 2 { None } else { Some(answer.max(-2)) };
+            assert_eq!(search_integer(-2, 2, |x| x >= answer), expected);
+        }
+        assert_eq!(search_integer(5, 4, |_| true), None);
+        assert_eq!(search_integer(i64::MIN, i64::MAX, |x| x >= 0), Some(0));
+        assert_eq!(search_integer(i64::MIN, i64::MAX, |_| true), Some(i64::MIN));
+        assert_eq!(search_integer(i64::MIN, i64::MAX, |x| x == i64::MAX), Some(i64::MAX));
+        assert_eq!(search_integer(i64::MIN, i64::MAX, |_| false), None);
+    }
+
+    #[test]
+    fn test_search_real() {
+        let cube_root = search_real(0.0, 30.0, 1e-12, |x| x * x * x >= 27.0).unwrap();
+        assert!((cube_root - 3.0).abs() < 1e-9);
+        assert_eq!(search_real(0.0, 1.0, 1e-9, |x| x > 2.0), None);
+        assert!(search_real(0.0, 1.0, 1e-9, |_| true).unwrap() <= 1e-9);
+        // An eps of zero stops when the interval cannot shrink any more
+        let exact = search_real(1.0, 2.0, 0.0, |x| x >= 1.5).unwrap();
+        assert_eq!(exact, 1.5);
+    }
+
+    #[test]
+    fn test_exponential_search() {
+        let mut calls = 0;
+        let found = exponential_search(10, |x| {
+            calls += 1;
+            x >= 1000
+        });
+        assert_eq!(found, Some(1000));
+        assert!(calls <= 2 * 11);
+        assert_eq!(exponential_search(7, |_| true), Some(7));
+        assert_eq!(exponential_search(i64::MAX - 3, |x| x == i64::MAX), Some(i64::MAX));
+        assert_eq!(exponential_search(0, |_| false), None);
+    }
+
+    #[test]
+    fn test_ternary_search() {
+        let peak = ternary_search(-100.0, 100.0, 1e-9, |x| -(x + 17.5).abs());
+        assert!((peak + 17.5).abs() < 1e-6);
+        // The peak on the boundary
+        let edge = ternary_search(0.0, 1.0, 1e-9, |x| x);
+        assert!((edge - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use super::resizing_array_queue::ResizingArrayQueue;
+
+/// The fundamentals bounded queue module represents a first-in-first-out (FIFO) queue with a fixed
+/// capacity that can be shared between threads, for producer/consumer pipelines.
+///
+/// `put` blocks while the queue is full and `take` blocks while it is empty. `try_put` and `try_take`
+/// give up after a timeout instead. After `close` no more items are accepted, but consumers keep
+/// taking the items that are left, and `take` returns `None` once the queue is closed and drained.
+///
+/// It is built on a `Mutex` around a `ResizingArrayQueue` and two `Condvar`s, one for "not full"
+/// and one for "not empty". The buffer is allocated for the full capacity up front and never shrinks
+/// below it, so `put` and `take` do not allocate, and every operation holds the lock for constant time: *O*(1).
+///
+/// # Examples
+/// ```
+/// use std::thread;
+/// use itualgs_rs::fundamentals::bounded_queue::BoundedQueue;
+///
+/// let queue = BoundedQueue::new(2);
+///
+/// let sum = thread::scope(|s| {
+///     let consumer = s.spawn(|| {
+///         let mut sum = 0;
+///         while let Some(x) = queue.take() {
+///             sum += x;
+///         }
+///         sum
+///     });
+///     for x in 1..=10 {
+///         queue.put(x).unwrap();
+///     }
+///     queue.close();
+///     consumer.join().unwrap()
+/// });
+///
+/// assert_eq!(sum, 55);
+/// ```
+pub struct BoundedQueue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+struct State<T> {
+    elements: ResizingArrayQueue<T>,
+    closed: bool,
+}
+
+/// The reason `try_put` did not add an item, the item is handed back
+#[derive(Debug, PartialEq, Eq)]
+pub enum PutError<T> {
+    /// The queue stayed full for the whole timeout
+    Timeout(T),
+    /// The queue has been closed
+    Closed(T),
+}
+
+impl<T> PutError<T> {
+    /// Returns the item that was not added
+    pub fn into_inner(self) -> T {
+        match self {
+            PutError::Timeout(val) | PutError::Closed(val) => val,
+        }
+    }
+}
+
+impl<T> fmt::Display for PutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PutError::Timeout(_) => write!(f, "timed out waiting for room in the queue"),
+            PutError::Closed(_) => write!(f, "the queue is closed"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for PutError<T> {}
+
+/// The reason `try_take` did not return an item
+#[derive(Debug, PartialEq, Eq)]
+pub enum TakeError {
+    /// The queue stayed empty for the whole timeout
+    Timeout,
+    /// The queue has been closed and every item has been taken
+    Closed,
+}
+
+impl fmt::Display for TakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TakeError::Timeout => write!(f, "timed out waiting for an item"),
+            TakeError::Closed => write!(f, "the queue is closed and drained"),
+        }
+    }
+}
+
+impl Error for TakeError {}
+
+impl<T> BoundedQueue<T> {
+    /// Create a new empty queue that holds at most `capacity` items.
+    pub fn new(capacity: usize) -> BoundedQueue<T> {
+        if capacity == 0 {
+            panic!("A bounded queue needs a positive capacity!")
+        }
+        BoundedQueue {
+            state: Mutex::new(State { elements: ResizingArrayQueue::with_capacity(capacity), closed: false }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    fn push(&self, mut state: MutexGuard<'_, State<T>>, val: T) {
+        state.elements.enqueue(val);
+        drop(state);
+        self.not_empty.notify_one();
+    }
+
+    fn pop(&self, mut state: MutexGuard<'_, State<T>>) -> Option<T> {
+        let val = state.elements.dequeue();
+        drop(state);
+        self.not_full.notify_one();
+        val
+    }
+
+    /// Adds an item to the back of the queue, waiting while the queue is full.
+    /// Hands the item back if the queue is closed.
+    pub fn put(&self, val: T) -> Result<(), T> {
+        let mut state = self.lock();
+        while !state.closed && state.elements.size() == self.capacity {
+            state = self.not_full.wait(state).unwrap();
+        }
+        if state.closed {
+            return Err(val);
+        }
+        self.push(state, val);
+        Ok(())
+    }
+
+    /// Adds an item to the back of the queue, waiting at most `timeout` for room.
+    pub fn try_put(&self, val: T, timeout: Duration) -> Result<(), PutError<T>> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        while !state.closed && state.elements.size() == self.capacity {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(PutError::Timeout(val));
+            }
+            state = self.not_full.wait_timeout(state, deadline - now).unwrap().0;
+        }
+        if state.closed {
+            return Err(PutError::Closed(val));
+        }
+        self.push(state, val);
+        Ok(())
+    }
+
+    /// Removes the item at the front of the queue, waiting while the queue is empty.
+    /// Returns `None` once the queue is closed and every item has been taken.
+    pub fn take(&self) -> Option<T> {
+        let mut state = self.lock();
+        while !state.closed && state.elements.is_empty() {
+            state = self.not_empty.wait(state).unwrap();
+        }
+        self.pop(state)
+    }
+
+    /// Removes the item at the front of the queue, waiting at most `timeout` for one to arrive.
+    pub fn try_take(&self, timeout: Duration) -> Result<T, TakeError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        while !state.closed && state.elements.is_empty() {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(TakeError::Timeout);
+            }
+            state = self.not_empty.wait_timeout(state, deadline - now).unwrap().0;
+        }
+        self.pop(state).ok_or(TakeError::Closed)
+    }
+
+    /// Stops the queue from accepting items and wakes every waiting thread.
+    /// Items already in the queue can still be taken.
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    /// Checks if the queue has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    /// Returns the number of items in the queue.
+    pub fn size(&self) -> usize {
+        self.lock().elements.size()
+    }
+
+    /// Checks if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().elements.is_empty()
+    }
+
+    /// Returns the maximum number of items the queue holds.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::{BoundedQueue, PutError, TakeError};
+
+    #[test]
+    fn test_fifo_and_capacity() {
+        let queue = BoundedQueue::new(2);
+        queue.put(1).unwrap();
+        queue.put(2).unwrap();
+        assert_eq!(queue.try_put(3, Duration::from_millis(10)), Err(PutError::Timeout(3)));
+        assert_eq!(queue.take(), Some(1));
+        assert_eq!(queue.try_take(Duration::from_millis(10)), Ok(2));
+        assert_eq!(queue.try_take(Duration::from_millis(10)), Err(TakeError::Timeout));
+    }
+
+    #[test]
+    fn test_buffer_is_not_reallocated() {
+        let queue = BoundedQueue::new(8);
+        for round in 0..3 {
+            for i in 0..8 {
+                queue.put(round * 8 + i).unwrap();
+            }
+            while queue.size() > 0 {
+                queue.take();
+            }
+            assert_eq!(queue.lock().elements.capacity(), 8);
+        }
+    }
+
+    #[test]
+    fn test_close_drains_then_stops() {
+        let queue = BoundedQueue::new(4);
+        queue.put("a").unwrap();
+        queue.put("b").unwrap();
+        queue.close();
+        assert!(queue.is_closed());
+        assert_eq!(queue.put("c"), Err("c"));
+        assert_eq!(queue.try_put("c", Duration::ZERO).unwrap_err().into_inner(), "c");
+        assert_eq!(queue.take(), Some("a"));
+        assert_eq!(queue.try_take(Duration::ZERO), Ok("b"));
+        assert_eq!(queue.take(), None);
+        assert_eq!(queue.try_take(Duration::ZERO), Err(TakeError::Closed));
+    }
+
+    #[test]
+    fn test_close_wakes_blocked_threads() {
+        let queue: BoundedQueue<u32> = BoundedQueue::new(1);
+        thread::scope(|s| {
+            let consumer = s.spawn(|| queue.take());
+            thread::sleep(Duration::from_millis(20));
+            queue.close();
+            assert_eq!(consumer.join().unwrap(), None);
+        });
+
+        let queue: BoundedQueue<u32> = BoundedQueue::new(1);
+        queue.put(1).unwrap();
+        thread::scope(|s| {
+            let producer = s.spawn(|| queue.put(2));
+            thread::sleep(Duration::from_millis(20));
+            queue.close();
+            assert_eq!(producer.join().unwrap(), Err(2));
+        });
+    }
+
+    #[test]
+    fn test_many_producers_and_consumers() {
+        let queue = BoundedQueue::new(8);
+        let producers: u64 = 4;
+        let per_producer: u64 = 5_000;
+
+        let taken: Vec<Vec<u64>> = thread::scope(|s| {
+            let consumers: Vec<_> = (0..3).map(|_| s.spawn(|| {
+                let mut taken = Vec::new();
+                while let Some(x) = queue.take() {
+                    taken.push(x);
+                }
+                taken
+            })).collect();
+
+            let handles: Vec<_> = (0..producers).map(|p| {
+                let queue = &queue;
+                s.spawn(move || {
+                    for i in 0..per_producer {
+                        queue.put(p * per_producer + i).unwrap();
+                    }
+                })
+            }).collect();
+            for h in handles {
+                h.join().unwrap();
+            }
+            queue.close();
+            consumers.into_iter().map(|c| c.join().unwrap()).collect()
+        });
+
+        // Every consumer sees the items of one producer in the order they were put
+        for items in &taken {
+            for p in 0..producers {
+                let own: Vec<&u64> = items.iter().filter(|x| **x / per_producer == p).collect();
+                assert!(own.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+        let mut all: Vec<u64> = taken.into_iter().flatten().collect();
+        all.sort();
+        assert_eq!(all, (0..producers * per_producer).collect::<Vec<u64>>());
+        assert!(queue.is_empty());
+    }
+}
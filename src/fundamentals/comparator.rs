@@ -0,0 +1,51 @@
+use std::cmp::Ordering;
+
+/// A Comparator decides the order of keys in an ordered collection, such as a `BST` or a `MinPQ`.
+/// Every comparison the collection makes goes through `compare`.
+///
+/// It is implemented for `Natural`, which uses the `Ord` implementation of the key,
+/// for `Reversed`, which turns another comparator around,
+/// and for every closure `Fn(&T, &T) -> Ordering`.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use itualgs_rs::fundamentals::comparator::{Comparator, Natural, Reversed};
+///
+/// assert_eq!(Natural.compare(&1, &2), Ordering::Less);
+/// assert_eq!(Reversed(Natural).compare(&1, &2), Ordering::Greater);
+///
+/// let by_len = |a: &&str, b: &&str| a.len().cmp(&b.len());
+/// assert_eq!(by_len.compare(&"ferris", &"rust"), Ordering::Greater);
+/// ```
+pub trait Comparator<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// The natural ordering of a key, given by its `Ord` implementation. This is the default comparator of every ordered collection.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Natural;
+
+impl<T: Ord> Comparator<T> for Natural {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Orders keys the opposite way of the comparator it wraps
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Reversed<C>(pub C);
+
+impl<T, C: Comparator<T>> Comparator<T> for Reversed<C> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+impl<T, F> Comparator<T> for F
+    where F: Fn(&T, &T) -> Ordering
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::{linked_list, LinkedList};
+
+use super::iter::delegate_iterator;
+
+#[derive(Debug)]
+/// The fundamentals deque module represents a double-ended queue of generic items.
+/// It generalizes both the stack and the queue, supporting adding and removing items at
+/// either end with <em>add_first</em>, <em>add_last</em>, <em>remove_first</em> and <em>remove_last</em>,
+/// along with peeking at both ends, getting the size, and testing if the deque is empty.
+///
+/// Every method should take constant worst-case running time: *O*(1).
+/// Iteration goes from the first to the last item, or backwards with `rev`.
+///
+/// This implementation uses a LinkedList\<T\>, to hold its elements.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::fundamentals::deque::Deque;
+///
+/// let mut deque = Deque::new();
+/// deque.add_last("Alice");
+/// deque.add_first("Bob");
+/// deque.add_last("Eve");
+/// // Bob Alice Eve
+///
+/// assert_eq!(deque.iter().rev().collect::<Vec<_>>(), vec![&"Eve", &"Alice", &"Bob"]);
+/// assert_eq!(deque.remove_last(), Some("Eve"));
+/// assert_eq!(deque.remove_first(), Some("Bob"));
+/// assert_eq!(deque.size(), 1);
+/// ```
+pub struct Deque<T> {
+    elements: LinkedList<T>,
+}
+
+impl<T> Deque<T> {
+    /// Create a new empty deque.
+    pub fn new() -> Deque<T> {
+        Deque {
+            elements: LinkedList::<T>::new()
+        }
+    }
+
+    /// Adds an element to the front of the deque.
+    pub fn add_first(&mut self, val: T) {
+        self.elements.push_front(val);
+    }
+
+    /// Adds an element to the back of the deque.
+    pub fn add_last(&mut self, val: T) {
+        self.elements.push_back(val);
+    }
+
+    /// Removes the element at the front of the deque.
+    pub fn remove_first(&mut self) -> Option<T> {
+        self.elements.pop_front()
+    }
+
+    /// Removes the element at the back of the deque.
+    pub fn remove_last(&mut self) -> Option<T> {
+        self.elements.pop_back()
+    }
+
+    /// Returns the element at the front of the deque.
+    pub fn peek_first(&self) -> Option<&T> {
+        self.elements.front()
+    }
+
+    /// Returns the element at the back of the deque.
+    pub fn peek_last(&self) -> Option<&T> {
+        self.elements.back()
+    }
+
+    /// Checks if the deque is empty.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns an iterator over the elements from the front to the back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { inner: self.elements.iter() }
+    }
+
+    /// Returns an iterator that allows modifying each element from the front to the back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { inner: self.elements.iter_mut() }
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Deque<T> {
+        Deque::new()
+    }
+}
+
+delegate_iterator! {
+    /// An iterator over the elements of a `Deque`, created by `Deque::iter`.
+    double_ended pub struct Iter<'a, T>(linked_list::Iter<'a, T>) -> &'a T;
+}
+
+delegate_iterator! {
+    /// A mutable iterator over the elements of a `Deque`, created by `Deque::iter_mut`.
+    double_ended pub struct IterMut<'a, T>(linked_list::IterMut<'a, T>) -> &'a mut T;
+}
+
+delegate_iterator! {
+    /// An owning iterator over the elements of a `Deque`, created by `into_iter`.
+    double_ended pub struct IntoIter<T>(linked_list::IntoIter<T>) -> T;
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self.elements.into_iter() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Deque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for Deque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.add_last(val);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Deque<T> {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deque;
+
+    #[test]
+    fn test_both_ends() {
+        let mut deque: Deque<u8> = Deque::new();
+        assert_eq!(deque.remove_first(), None);
+        assert_eq!(deque.remove_last(), None);
+        deque.add_first(2);
+        deque.add_first(1);
+        deque.add_last(3);
+        assert_eq!(deque.peek_first(), Some(&1));
+        assert_eq!(deque.peek_last(), Some(&3));
+        assert_eq!(deque.remove_last(), Some(3));
+        assert_eq!(deque.remove_last(), Some(2));
+        assert_eq!(deque.remove_last(), Some(1));
+        assert!(deque.is_empty());
+    }
+
+    #[test]
+    fn test_double_ended_iteration() {
+        let mut deque: Deque<u8> = (1..=4).collect();
+        for val in &mut deque {
+            *val *= 10;
+        }
+        let mut it = deque.iter();
+        assert_eq!(it.next(), Some(&10));
+        assert_eq!(it.next_back(), Some(&40));
+        assert_eq!(it.len(), 2);
+        assert_eq!(deque.into_iter().rev().collect::<Vec<_>>(), vec![40, 30, 20, 10]);
+    }
+}
@@ -0,0 +1,566 @@
+use std::{borrow::Borrow, collections::HashMap, error::Error, fmt, hash::Hash};
+
+use super::stack::Stack;
+
+/// The fundamentals evaluate module evaluates arithmetic expressions, after Dijkstra's two-stack
+/// algorithm from Section 1.3 of Algorithms, 4th Edition by Robert Sedgewick and Kevin Wayne.
+///
+/// The original only reads fully parenthesized expressions. This one is a shunting-yard parser:
+/// it reads the expression left to right, keeps pending operators on one `Stack`, and pops them
+/// by precedence and associativity instead of waiting for a closing parenthesis. Popped operators
+/// either go to a `Stack` of values, which evaluates the expression, or to a list of tokens in
+/// reverse Polish notation (RPN), which can be evaluated later, also with other variables.
+///
+/// It knows, from loosest to tightest binding:
+/// - `+` and `-`, left associative
+/// - `*` and `/`, left associative
+/// - unary minus, so `-2^2` is `-4` and `2*-3` is `-6`
+/// - `^` (power), right associative, so `2^3^2` is `2^9`
+/// - the functions `sqrt(x)`, `min(x, ...)` and `max(x, ...)`
+/// - numbers like `3`, `0.25` or `1e-3`, and named variables like `rate` or `x_1`
+///
+/// Malformed input gives an `ExprError` with the byte position where the problem was found.
+/// Parsing and evaluating take linear time in the length of the expression: *O*(n).
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use itualgs_rs::fundamentals::evaluate::{evaluate, evaluate_with, to_rpn, ExprErrorKind};
+///
+/// // Sedgewick's example still works
+/// assert_eq!(evaluate("( 1 + ( ( 2 + 3 ) * ( 4 * 5 ) ) )"), Ok(101.0));
+/// assert_eq!(evaluate("1 + 2 * 3 - 4"), Ok(3.0));
+/// assert_eq!(evaluate("max(2, sqrt(16)) ^ 2"), Ok(16.0));
+///
+/// let vars = HashMap::from([("width", 3.0), ("height", 4.0)]);
+/// assert_eq!(evaluate_with("sqrt(width^2 + height^2)", &vars), Ok(5.0));
+///
+/// let rpn = to_rpn("-(a + b) * c").unwrap();
+/// assert_eq!(rpn.to_string(), "a b + neg c *");
+/// assert_eq!(rpn.eval(&HashMap::from([("a", 1.0), ("b", 2.0), ("c", 10.0)])), Ok(-30.0));
+///
+/// let err = evaluate("2 * (3 + )").unwrap_err();
+/// assert_eq!(err.kind, ExprErrorKind::ExpectedOperand);
+/// assert_eq!(err.position, 9);
+/// ```
+pub fn evaluate(expr: &str) -> Result<f64, ExprError> {
+    evaluate_with(expr, &HashMap::<&str, f64>::new())
+}
+
+/// Evaluates an expression, looking up variables in `vars`.
+pub fn evaluate_with<K>(expr: &str, vars: &HashMap<K, f64>) -> Result<f64, ExprError>
+where
+    K: Borrow<str> + Hash + Eq,
+{
+    let mut values = Values { values: Stack::new(), vars };
+    parse(expr, &mut values)?;
+    Ok(values.result())
+}
+
+/// Parses an expression into reverse Polish notation.
+pub fn to_rpn(expr: &str) -> Result<Rpn, ExprError> {
+    let mut rpn = Rpn { tokens: Vec::new(), positions: Vec::new() };
+    parse(expr, &mut rpn)?;
+    Ok(rpn)
+}
+
+/// A binary or unary operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Neg,
+}
+
+impl Operator {
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::Add | Operator::Sub => 1,
+            Operator::Mul | Operator::Div => 2,
+            Operator::Neg => 3,
+            Operator::Pow => 4,
+        }
+    }
+
+    fn right_associative(&self) -> bool {
+        matches!(self, Operator::Pow | Operator::Neg)
+    }
+}
+
+/// A function that can be called in an expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+    Sqrt,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "sqrt" => Some(Function::Sqrt),
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Function::Sqrt => "sqrt",
+            Function::Min => "min",
+            Function::Max => "max",
+        }
+    }
+
+    fn accepts(&self, args: usize) -> bool {
+        match self {
+            Function::Sqrt => args == 1,
+            Function::Min | Function::Max => args >= 1,
+        }
+    }
+}
+
+/// A token of an expression in reverse Polish notation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Number(f64),
+    Variable(String),
+    Operator(Operator),
+    /// A function and the number of arguments it was called with
+    Function(Function, usize),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(x) => write!(f, "{}", x),
+            Token::Variable(name) => write!(f, "{}", name),
+            Token::Operator(op) => write!(f, "{}", match op {
+                Operator::Add => "+",
+                Operator::Sub => "-",
+                Operator::Mul => "*",
+                Operator::Div => "/",
+                Operator::Pow => "^",
+                Operator::Neg => "neg",
+            }),
+            Token::Function(func, 1) => write!(f, "{}", func.name()),
+            Token::Function(func, args) => write!(f, "{}/{}", func.name(), args),
+        }
+    }
+}
+
+/// What went wrong in an expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprErrorKind {
+    /// A character that is not part of any token
+    UnexpectedChar(char),
+    /// A number that does not parse, like `1.2.3`
+    InvalidNumber(String),
+    /// A value, variable or parenthesis where an operator was expected
+    ExpectedOperator,
+    /// An operator, comma or closing parenthesis where a value was expected
+    ExpectedOperand,
+    /// The expression ended where a value was expected
+    UnexpectedEnd,
+    /// A parenthesis without a partner
+    UnbalancedParenthesis,
+    /// A comma outside of a function call
+    UnexpectedComma,
+    /// A name followed by `(` that is not a known function
+    UnknownFunction(String),
+    /// A function called with the wrong number of arguments
+    WrongArity { function: &'static str, args: usize },
+    /// A variable that was not given a value
+    UnknownVariable(String),
+}
+
+/// An error in an expression, with the byte position where it was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprError {
+    pub kind: ExprErrorKind,
+    pub position: usize,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ExprErrorKind::UnexpectedChar(c) => write!(f, "unexpected character '{}'", c)?,
+            ExprErrorKind::InvalidNumber(s) => write!(f, "invalid number '{}'", s)?,
+            ExprErrorKind::ExpectedOperator => write!(f, "expected an operator")?,
+            ExprErrorKind::ExpectedOperand => write!(f, "expected a value")?,
+            ExprErrorKind::UnexpectedEnd => write!(f, "unexpected end of expression")?,
+            ExprErrorKind::UnbalancedParenthesis => write!(f, "unbalanced parenthesis")?,
+            ExprErrorKind::UnexpectedComma => write!(f, "comma outside of a function call")?,
+            ExprErrorKind::UnknownFunction(name) => write!(f, "unknown function '{}'", name)?,
+            ExprErrorKind::WrongArity { function, args } => write!(f, "{} does not take {} arguments", function, args)?,
+            ExprErrorKind::UnknownVariable(name) => write!(f, "unknown variable '{}'", name)?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ExprError {}
+
+fn error<T>(kind: ExprErrorKind, position: usize) -> Result<T, ExprError> {
+    Err(ExprError { kind, position })
+}
+
+/// An expression in reverse Polish notation, created by `to_rpn`.
+/// Displays as its tokens separated by spaces, with `neg` for unary minus
+/// and `min/3` for a function called with 3 arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rpn {
+    tokens: Vec<Token>,
+    positions: Vec<usize>,
+}
+
+impl Rpn {
+    /// Returns the tokens in the order they are evaluated.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Evaluates the expression, looking up variables in `vars`.
+    pub fn eval<K>(&self, vars: &HashMap<K, f64>) -> Result<f64, ExprError>
+    where
+        K: Borrow<str> + Hash + Eq,
+    {
+        let mut values = Values { values: Stack::new(), vars };
+        for (token, position) in self.tokens.iter().zip(&self.positions) {
+            values.emit(token.clone(), *position)?;
+        }
+        Ok(values.result())
+    }
+}
+
+impl fmt::Display for Rpn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, token) in self.tokens.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", token)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the tokens of an expression in reverse Polish order as the parser pops them
+trait Output {
+    fn emit(&mut self, token: Token, position: usize) -> Result<(), ExprError>;
+}
+
+impl Output for Rpn {
+    fn emit(&mut self, token: Token, position: usize) -> Result<(), ExprError> {
+        self.tokens.push(token);
+        self.positions.push(position);
+        Ok(())
+    }
+}
+
+/// The value stack of Dijkstra's algorithm
+struct Values<'a, K> {
+    values: Stack<f64>,
+    vars: &'a HashMap<K, f64>,
+}
+
+impl<K> Values<'_, K> {
+    fn pop(&mut self) -> f64 {
+        // The parser only emits an operator after its operands
+        self.values.pop().expect("operand missing from the value stack")
+    }
+
+    fn result(mut self) -> f64 {
+        self.pop()
+    }
+}
+
+impl<K: Borrow<str> + Hash + Eq> Output for Values<'_, K> {
+    fn emit(&mut self, token: Token, position: usize) -> Result<(), ExprError> {
+        let value = match token {
+            Token::Number(x) => x,
+            Token::Variable(name) => match self.vars.get(name.as_str()) {
+                Some(x) => *x,
+                None => return error(ExprErrorKind::UnknownVariable(name), position),
+            },
+            Token::Operator(Operator::Neg) => -self.pop(),
+            Token::Operator(op) => {
+                let b = self.pop();
+                let a = self.pop();
+                match op {
+                    Operator::Add => a + b,
+                    Operator::Sub => a - b,
+                    Operator::Mul => a * b,
+                    Operator::Div => a / b,
+                    Operator::Pow => a.powf(b),
+                    Operator::Neg => unreachable!(),
+                }
+            }
+            Token::Function(Function::Sqrt, _) => self.pop().sqrt(),
+            Token::Function(func, args) => {
+                let mut result = self.pop();
+                for _ in 1..args {
+                    let x = self.pop();
+                    result = if func == Function::Min { result.min(x) } else { result.max(x) };
+                }
+                result
+            }
+        };
+        self.values.push(value);
+        Ok(())
+    }
+}
+
+/// An entry of the operator stack
+enum Pending {
+    Operator(Operator),
+    /// An opening parenthesis, of a function call if it has a function, with the number of arguments so far
+    Paren(Option<Function>, usize),
+}
+
+/// The shunting-yard algorithm, sending tokens to `out` in reverse Polish order
+fn parse(expr: &str, out: &mut impl Output) -> Result<(), ExprError> {
+    let bytes = expr.as_bytes();
+    let mut ops: Stack<(Pending, usize)> = Stack::new();
+    let mut expect_operand = true;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || c == b'.' {
+            i = scan_number(bytes, i);
+            if !expect_operand {
+                return error(ExprErrorKind::ExpectedOperator, start);
+            }
+            let text = &expr[start..i];
+            let x = text.parse()
+                .map_err(|_| ExprError { kind: ExprErrorKind::InvalidNumber(text.to_string()), position: start })?;
+            out.emit(Token::Number(x), start)?;
+            expect_operand = false;
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            if !expect_operand {
+                return error(ExprErrorKind::ExpectedOperator, start);
+            }
+            let name = &expr[start..i];
+            let mut next = i;
+            while next < bytes.len() && bytes[next].is_ascii_whitespace() {
+                next += 1;
+            }
+            if next < bytes.len() && bytes[next] == b'(' {
+                let func = Function::from_name(name)
+                    .ok_or_else(|| ExprError { kind: ExprErrorKind::UnknownFunction(name.to_string()), position: start })?;
+                ops.push((Pending::Paren(Some(func), 1), start));
+                i = next + 1;
+            } else {
+                out.emit(Token::Variable(name.to_string()), start)?;
+                expect_operand = false;
+            }
+        } else {
+            i += 1;
+            match c {
+                b'(' => {
+                    if !expect_operand {
+                        return error(ExprErrorKind::ExpectedOperator, start);
+                    }
+                    ops.push((Pending::Paren(None, 1), start));
+                }
+                b')' | b',' => {
+                    if expect_operand {
+                        return error(ExprErrorKind::ExpectedOperand, start);
+                    }
+                    let paren = pop_to_paren(&mut ops, out)?;
+                    if c == b',' && !matches!(paren, Some((Some(_), _, _))) {
+                        return error(ExprErrorKind::UnexpectedComma, start);
+                    }
+                    let (func, args, open) = paren
+                        .ok_or(ExprError { kind: ExprErrorKind::UnbalancedParenthesis, position: start })?;
+                    if c == b',' {
+                        ops.push((Pending::Paren(func, args + 1), open));
+                        expect_operand = true;
+                    } else if let Some(func) = func {
+                        if !func.accepts(args) {
+                            return error(ExprErrorKind::WrongArity { function: func.name(), args }, open);
+                        }
+                        out.emit(Token::Function(func, args), open)?;
+                    }
+                }
+                b'+' | b'-' | b'*' | b'/' | b'^' => {
+                    if expect_operand {
+                        match c {
+                            b'-' => ops.push((Pending::Operator(Operator::Neg), start)),
+                            // A unary plus changes nothing
+                            b'+' => (),
+                            _ => return error(ExprErrorKind::ExpectedOperand, start),
+                        }
+                        continue;
+                    }
+                    let op = match c {
+                        b'+' => Operator::Add,
+                        b'-' => Operator::Sub,
+                        b'*' => Operator::Mul,
+                        b'/' => Operator::Div,
+                        _ => Operator::Pow,
+                    };
+                    while let Some((Pending::Operator(top), _)) = ops.peek() {
+                        let pops = top.precedence() > op.precedence()
+                            || (top.precedence() == op.precedence() && !op.right_associative());
+                        if !pops {
+                            break;
+                        }
+                        let (top, position) = ops.pop().unwrap();
+                        if let Pending::Operator(top) = top {
+                            out.emit(Token::Operator(top), position)?;
+                        }
+                    }
+                    ops.push((Pending::Operator(op), start));
+                    expect_operand = true;
+                }
+                _ => {
+                    let c = expr[start..].chars().next().unwrap();
+                    return error(ExprErrorKind::UnexpectedChar(c), start);
+                }
+            }
+            // Skip the rest of a multi-byte character
+            while i < bytes.len() && !expr.is_char_boundary(i) {
+                i += 1;
+            }
+        }
+    }
+
+    if expect_operand {
+        return error(ExprErrorKind::UnexpectedEnd, expr.len());
+    }
+    while let Some((pending, position)) = ops.pop() {
+        match pending {
+            Pending::Operator(op) => out.emit(Token::Operator(op), position)?,
+            Pending::Paren(..) => return error(ExprErrorKind::UnbalancedParenthesis, position),
+        }
+    }
+    Ok(())
+}
+
+/// Pops operators to `out` until an opening parenthesis, which is returned with its position
+fn pop_to_paren(ops: &mut Stack<(Pending, usize)>, out: &mut impl Output) -> Result<Option<(Option<Function>, usize, usize)>, ExprError> {
+    while let Some((pending, position)) = ops.pop() {
+        match pending {
+            Pending::Operator(op) => out.emit(Token::Operator(op), position)?,
+            Pending::Paren(func, args) => return Ok(Some((func, args, position))),
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the end of the number starting at `i`, with an optional fraction and exponent
+fn scan_number(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+        i += 1;
+    }
+    if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+        let mut j = i + 1;
+        if j < bytes.len() && (bytes[j] == b'+' || bytes[j] == b'-') {
+            j += 1;
+        }
+        if j < bytes.len() && bytes[j].is_ascii_digit() {
+            i = j;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{evaluate, evaluate_with, to_rpn, ExprError, ExprErrorKind, Function, Token};
+
+    fn fails(expr: &str, kind: ExprErrorKind, position: usize) {
+        assert_eq!(evaluate(expr), Err(ExprError { kind, position }), "{}", expr);
+    }
+
+    #[test]
+    fn test_precedence_and_associativity() {
+        assert_eq!(evaluate("2 + 3 * 4"), Ok(14.0));
+        assert_eq!(evaluate("(2 + 3) * 4"), Ok(20.0));
+        assert_eq!(evaluate("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(evaluate("64 / 4 / 2"), Ok(8.0));
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(evaluate("2*3^2"), Ok(18.0));
+        assert_eq!(evaluate("1.5e1 + .5"), Ok(15.5));
+    }
+
+    #[test]
+    fn test_unary_minus() {
+        assert_eq!(evaluate("-2 ^ 2"), Ok(-4.0));
+        assert_eq!(evaluate("(-2) ^ 2"), Ok(4.0));
+        assert_eq!(evaluate("2 ^ -1"), Ok(0.5));
+        assert_eq!(evaluate("3 - -2"), Ok(5.0));
+        assert_eq!(evaluate("--3"), Ok(3.0));
+        assert_eq!(evaluate("-3 * -3"), Ok(9.0));
+        assert_eq!(evaluate("+4"), Ok(4.0));
+    }
+
+    #[test]
+    fn test_functions_and_variables() {
+        let vars: HashMap<String, f64> = HashMap::from([("x".to_string(), 9.0), ("y_2".to_string(), -1.0)]);
+        assert_eq!(evaluate_with("sqrt(x) + y_2", &vars), Ok(2.0));
+        assert_eq!(evaluate_with("min(x, 3, y_2 * 7)", &vars), Ok(-7.0));
+        assert_eq!(evaluate_with("max (x)", &vars), Ok(9.0));
+        assert_eq!(evaluate_with("max(min(1, 2), sqrt(x) - 1) * 2", &vars), Ok(4.0));
+        assert_eq!(
+            evaluate_with("x + z", &vars),
+            Err(ExprError { kind: ExprErrorKind::UnknownVariable("z".to_string()), position: 4 })
+        );
+    }
+
+    #[test]
+    fn test_rpn() {
+        let rpn = to_rpn("3 + 4 * 2 / (1 - 5) ^ 2 ^ 3").unwrap();
+        assert_eq!(rpn.to_string(), "3 4 2 * 1 5 - 2 3 ^ ^ / +");
+        let rpn = to_rpn("max(a, b, 1) - sqrt(c)").unwrap();
+        assert_eq!(rpn.to_string(), "a b 1 max/3 c sqrt -");
+        assert_eq!(rpn.tokens()[3], Token::Function(Function::Max, 3));
+
+        let vars = HashMap::from([("a", 4.0), ("b", 7.0), ("c", 25.0)]);
+        assert_eq!(rpn.eval(&vars), Ok(2.0));
+        let vars = HashMap::from([("a", 40.0), ("b", 7.0), ("c", 4.0)]);
+        assert_eq!(rpn.eval(&vars), Ok(38.0));
+    }
+
+    #[test]
+    fn test_errors_have_positions() {
+        fails("", ExprErrorKind::UnexpectedEnd, 0);
+        fails("1 +", ExprErrorKind::UnexpectedEnd, 3);
+        fails("1 2", ExprErrorKind::ExpectedOperator, 2);
+        fails("2 (3)", ExprErrorKind::ExpectedOperator, 2);
+        fails("* 2", ExprErrorKind::ExpectedOperand, 0);
+        fails("()", ExprErrorKind::ExpectedOperand, 1);
+        fails("(1 + 2", ExprErrorKind::UnbalancedParenthesis, 0);
+        fails("1 + 2)", ExprErrorKind::UnbalancedParenthesis, 5);
+        fails("1, 2", ExprErrorKind::UnexpectedComma, 1);
+        fails("(1, 2)", ExprErrorKind::UnexpectedComma, 2);
+        fails("3 $ 4", ExprErrorKind::UnexpectedChar('$'), 2);
+        fails("3 + é", ExprErrorKind::UnexpectedChar('é'), 4);
+        fails("1.2.3", ExprErrorKind::InvalidNumber("1.2.3".to_string()), 0);
+        fails("cos(1)", ExprErrorKind::UnknownFunction("cos".to_string()), 0);
+        fails("1 + sqrt(1, 2)", ExprErrorKind::WrongArity { function: "sqrt", args: 2 }, 4);
+        fails("max()", ExprErrorKind::ExpectedOperand, 4);
+        fails("max(1,)", ExprErrorKind::ExpectedOperand, 6);
+        assert_eq!(evaluate("(1 +").unwrap_err().to_string(), "unexpected end of expression at position 4");
+    }
+}
@@ -0,0 +1,50 @@
+/// Defines an iterator type that wraps the iterator of the underlying storage of a collection, so the
+/// collection can change its storage without changing its public iterator types.
+///
+/// The wrapper forwards `Iterator`, `ExactSizeIterator` and `FusedIterator` to the inner iterator,
+/// and also `DoubleEndedIterator` when the definition is marked `double_ended`.
+///
+/// ```ignore
+/// delegate_iterator! {
+///     /// An iterator over the elements of a `Stack`, created by `Stack::iter`.
+///     pub struct Iter<'a, T>(Rev<slice::Iter<'a, T>>) -> &'a T;
+/// }
+/// ```
+macro_rules! delegate_iterator {
+    ($(#[$attr:meta])* double_ended pub struct $name:ident<$($lt:lifetime,)? $t:ident>($inner:ty) -> $item:ty;) => {
+        delegate_iterator! {
+            $(#[$attr])*
+            pub struct $name<$($lt,)? $t>($inner) -> $item;
+        }
+
+        impl<$($lt,)? $t> DoubleEndedIterator for $name<$($lt,)? $t> {
+            fn next_back(&mut self) -> Option<$item> {
+                self.inner.next_back()
+            }
+        }
+    };
+    ($(#[$attr:meta])* pub struct $name:ident<$($lt:lifetime,)? $t:ident>($inner:ty) -> $item:ty;) => {
+        $(#[$attr])*
+        pub struct $name<$($lt,)? $t> {
+            inner: $inner,
+        }
+
+        impl<$($lt,)? $t> Iterator for $name<$($lt,)? $t> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<$item> {
+                self.inner.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        impl<$($lt,)? $t> ExactSizeIterator for $name<$($lt,)? $t> {}
+
+        impl<$($lt,)? $t> std::iter::FusedIterator for $name<$($lt,)? $t> {}
+    };
+}
+
+pub(crate) use delegate_iterator;
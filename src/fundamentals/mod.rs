@@ -1,5 +1,13 @@
+mod iter;
 pub mod stack;
+pub mod evaluate;
 pub mod queue;
+pub mod resizing_array_queue;
+pub mod deque;
+pub mod randomized_queue;
+pub mod bounded_queue;
 pub mod bag;
 pub mod binary_search;
-pub mod uf;
\ No newline at end of file
+pub mod comparator;
+pub mod uf;
+pub mod percolation;
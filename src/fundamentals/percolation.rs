@@ -0,0 +1,261 @@
+use crate::randomization::lcg_random::Lcg;
+
+use super::uf::WeightedQuickUnionUF;
+
+/// The fundamentals percolation module models an n-by-n grid of sites, each either open or blocked.
+/// A site is <em>full</em> if it is open and connected to the top row through a chain of open
+/// neighbouring sites, and the system <em>percolates</em> if some site in the bottom row is full.
+///
+/// Rows and columns are numbered from 1 to n, as in Section 1.5 of Algorithms, 4th Edition by
+/// Robert Sedgewick and Kevin Wayne.
+///
+/// Connectivity is kept in a `WeightedQuickUnionUF` with a virtual top site joined to the whole top row
+/// and a virtual bottom site joined to the whole bottom row, so `percolates` is a single `connected` call.
+/// A second union find without the virtual bottom answers `is_full`, otherwise every open site joined
+/// to the bottom row would look full once the system percolates ("backwash").
+/// Open, is_full and percolates take *O*(log n) time, is_open takes constant time.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::percolation::Percolation;
+///
+/// let mut grid = Percolation::new(3);
+/// grid.open(1, 1);
+/// grid.open(2, 1);
+/// grid.open(3, 3);
+/// assert!(grid.is_full(2, 1));
+/// assert!(!grid.percolates());
+///
+/// grid.open(3, 1);
+/// assert!(grid.percolates());
+/// // Open, and connected to the bottom, but not to the top
+/// assert!(!grid.is_full(3, 3));
+/// assert_eq!(grid.number_of_open_sites(), 4);
+/// ```
+pub struct Percolation {
+    n: usize,
+    open: Vec<bool>,
+    open_count: usize,
+    uf: WeightedQuickUnionUF,
+    full: WeightedQuickUnionUF,
+}
+
+impl Percolation {
+    /// Create an n-by-n grid with every site blocked
+    pub fn new(n: usize) -> Percolation {
+        if n == 0 {
+            panic!("The grid needs at least one site!")
+        }
+        Percolation {
+            n,
+            open: vec![false; n * n],
+            open_count: 0,
+            uf: WeightedQuickUnionUF::new(n * n + 2),
+            full: WeightedQuickUnionUF::new(n * n + 1),
+        }
+    }
+
+    fn top(&self) -> usize {
+        self.n * self.n
+    }
+
+    fn bottom(&self) -> usize {
+        self.n * self.n + 1
+    }
+
+    /// Maps a row and column from 1 to n to the index of the site
+    fn index(&self, row: usize, col: usize) -> usize {
+        if row == 0 || row > self.n || col == 0 || col > self.n {
+            panic!("Site ({}, {}) is outside the grid!", row, col)
+        }
+        (row - 1) * self.n + (col - 1)
+    }
+
+    /// Opens the site at (row, col) if it is not open already
+    pub fn open(&mut self, row: usize, col: usize) {
+        let p = self.index(row, col);
+        if self.open[p] {
+            return;
+        }
+        self.open[p] = true;
+        self.open_count += 1;
+
+        if row == 1 {
+            self.uf.union(p, self.top());
+            self.full.union(p, self.top());
+        }
+        if row == self.n {
+            self.uf.union(p, self.bottom());
+        }
+        let neighbours = [
+            (row > 1).then(|| (row - 1, col)),
+            (row < self.n).then(|| (row + 1, col)),
+            (col > 1).then(|| (row, col - 1)),
+            (col < self.n).then(|| (row, col + 1)),
+        ];
+        for (r, c) in neighbours.into_iter().flatten() {
+            let q = self.index(r, c);
+            if self.open[q] {
+                self.uf.union(p, q);
+                self.full.union(p, q);
+            }
+        }
+    }
+
+    /// Checks if the site at (row, col) is open
+    pub fn is_open(&self, row: usize, col: usize) -> bool {
+        self.open[self.index(row, col)]
+    }
+
+    /// Checks if the site at (row, col) is connected to the top row
+    pub fn is_full(&self, row: usize, col: usize) -> bool {
+        let p = self.index(row, col);
+        self.open[p] && self.full.connected(p, self.top())
+    }
+
+    /// Returns the number of open sites
+    pub fn number_of_open_sites(&self) -> usize {
+        self.open_count
+    }
+
+    /// Checks if the system percolates
+    pub fn percolates(&self) -> bool {
+        self.uf.connected(self.top(), self.bottom())
+    }
+}
+
+/// The percolation stats runner estimates the percolation threshold of an n-by-n grid
+/// with a Monte Carlo simulation: every trial opens sites in a uniformly random order until the
+/// grid percolates, and the fraction of open sites at that point is one sample of the threshold.
+///
+/// The randomness comes from the seedable `randomization::lcg_random::Lcg`. `new` seeds it with the
+/// time, while `with_seed` makes every estimate repeatable. The threshold of a large grid is about 0.593.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::percolation::PercolationStats;
+///
+/// let stats = PercolationStats::with_seed(50, 40, 7);
+/// assert!(stats.mean() > 0.55 && stats.mean() < 0.63);
+/// assert!(stats.confidence_lo() < stats.mean() && stats.mean() < stats.confidence_hi());
+/// ```
+pub struct PercolationStats {
+    thresholds: Vec<f64>,
+}
+
+impl PercolationStats {
+    /// Runs `trials` experiments on an n-by-n grid, seeded with the current time
+    pub fn new(n: usize, trials: usize) -> PercolationStats {
+        Self::run(n, trials, Lcg::from_time())
+    }
+
+    /// Runs `trials` experiments on an n-by-n grid, with random choices determined by `seed`
+    pub fn with_seed(n: usize, trials: usize, seed: u64) -> PercolationStats {
+        Self::run(n, trials, Lcg::new(seed))
+    }
+
+    fn run(n: usize, trials: usize, mut rng: Lcg) -> PercolationStats {
+        if n == 0 || trials == 0 {
+            panic!("Both the grid size and the number of trials must be positive!")
+        }
+        let mut sites: Vec<usize> = (0..n * n).collect();
+        let mut thresholds = Vec::with_capacity(trials);
+        for _ in 0..trials {
+            rng.shuffle(&mut sites);
+            let mut grid = Percolation::new(n);
+            for p in &sites {
+                grid.open(p / n + 1, p % n + 1);
+                if grid.percolates() {
+                    break;
+                }
+            }
+            thresholds.push(grid.number_of_open_sites() as f64 / (n * n) as f64);
+        }
+        PercolationStats { thresholds }
+    }
+
+    /// Returns the sample mean of the percolation threshold
+    pub fn mean(&self) -> f64 {
+        self.thresholds.iter().sum::<f64>() / self.thresholds.len() as f64
+    }
+
+    /// Returns the sample standard deviation of the percolation threshold, NaN for a single trial
+    pub fn stddev(&self) -> f64 {
+        let mean = self.mean();
+        let squares: f64 = self.thresholds.iter().map(|x| (x - mean) * (x - mean)).sum();
+        (squares / (self.thresholds.len() as f64 - 1.0)).sqrt()
+    }
+
+    /// Returns the half width of the 95% confidence interval
+    fn half_width(&self) -> f64 {
+        1.96 * self.stddev() / (self.thresholds.len() as f64).sqrt()
+    }
+
+    /// Returns the low endpoint of the 95% confidence interval
+    pub fn confidence_lo(&self) -> f64 {
+        self.mean() - self.half_width()
+    }
+
+    /// Returns the high endpoint of the 95% confidence interval
+    pub fn confidence_hi(&self) -> f64 {
+        self.mean() + self.half_width()
+    }
+
+    /// Returns the threshold found in every trial
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Percolation, PercolationStats};
+
+    #[test]
+    fn test_single_site() {
+        let mut grid = Percolation::new(1);
+        assert!(!grid.percolates());
+        assert!(!grid.is_full(1, 1));
+        grid.open(1, 1);
+        grid.open(1, 1);
+        assert!(grid.percolates());
+        assert!(grid.is_full(1, 1));
+        assert_eq!(grid.number_of_open_sites(), 1);
+    }
+
+    #[test]
+    fn test_no_backwash() {
+        let mut grid = Percolation::new(4);
+        // A column down the left side
+        for row in 1..=4 {
+            grid.open(row, 1);
+        }
+        // A site on the bottom row joined to the column only through the virtual bottom
+        grid.open(4, 3);
+        grid.open(3, 3);
+        assert!(grid.percolates());
+        assert!(grid.is_full(4, 1));
+        assert!(grid.is_open(3, 3));
+        assert!(!grid.is_full(3, 3));
+        assert!(!grid.is_full(4, 3));
+        assert!(!grid.is_open(2, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_outside_grid() {
+        Percolation::new(3).open(0, 1);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = PercolationStats::with_seed(20, 30, 3);
+        assert_eq!(stats.thresholds().len(), 30);
+        assert!(stats.thresholds().iter().all(|t| *t > 0.0 && *t <= 1.0));
+        assert!(stats.stddev() > 0.0);
+        assert!(stats.confidence_lo() > 0.5 && stats.confidence_hi() < 0.7);
+
+        let again = PercolationStats::with_seed(20, 30, 3);
+        assert_eq!(stats.thresholds(), again.thresholds());
+    }
+}
@@ -1,4 +1,6 @@
-use std::collections::LinkedList;
+use std::collections::{linked_list, LinkedList};
+
+use super::iter::delegate_iterator;
 
 #[derive(Debug)]
 /// The fundamentals queue module represents a first-in-first-out (FIFO) queue 
@@ -86,11 +88,110 @@ impl<T> Default for Queue<T> {
     }
 }
 
+impl<T> Queue<T> {
+    /// Returns an iterator over the elements from the front to the back (FIFO).
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { inner: self.elements.iter() }
+    }
+
+    /// Returns an iterator that allows modifying each element from the front to the back (FIFO).
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { inner: self.elements.iter_mut() }
+    }
+}
+
+delegate_iterator! {
+    /// An iterator over the elements of a `Queue`, created by `Queue::iter`.
+    pub struct Iter<'a, T>(linked_list::Iter<'a, T>) -> &'a T;
+}
+
+delegate_iterator! {
+    /// A mutable iterator over the elements of a `Queue`, created by `Queue::iter_mut`.
+    pub struct IterMut<'a, T>(linked_list::IterMut<'a, T>) -> &'a mut T;
+}
+
+delegate_iterator! {
+    /// An owning iterator over the elements of a `Queue`, created by `into_iter`.
+    pub struct IntoIter<T>(linked_list::IntoIter<T>) -> T;
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self.elements.into_iter() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Queue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.enqueue(val);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Queue<T> {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+
+/// Serializes the queue as a sequence from the front to the back
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Queue<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.elements)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Queue<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Queue { elements: LinkedList::deserialize(deserializer)? })
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::Queue;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut queue: Queue<u8> = Queue::new();
+        queue.enqueue(20);
+        queue.enqueue(30);
+        let json = serde_json::to_string(&queue).unwrap();
+        assert_eq!(json, "[20,30]");
+
+        let mut back: Queue<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.dequeue(), Some(20));
+        assert_eq!(back.dequeue(), Some(30));
+    }
+
     #[test]
     fn test_push() {
         let mut queue: Queue<u8> = Queue::new();
@@ -126,6 +227,20 @@ mod tests {
         assert_eq!(queue.size(), 2);
     }
 
+    #[test]
+    fn test_iter_is_fifo() {
+        let mut queue: Queue<u8> = (1..=3).collect();
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        for val in &mut queue {
+            *val *= 10;
+        }
+        queue.extend(vec![40, 50]);
+        assert_eq!(queue.peek(), Some(&10));
+        assert_eq!(queue.iter().len(), 5);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![10, 20, 30, 40, 50]);
+    }
+
     #[test]
     fn test_is_empty() {
         let mut queue: Queue<u8> = Queue::new();
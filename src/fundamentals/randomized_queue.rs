@@ -0,0 +1,222 @@
+use std::{cell::Cell, vec};
+
+use crate::randomization::lcg_random::Lcg;
+
+use super::iter::delegate_iterator;
+
+#[derive(Debug)]
+/// The fundamentals randomized queue module represents a queue of generic items where the item
+/// removed is chosen uniformly at random among the items in the queue. It supports <em>enqueue</em>,
+/// <em>dequeue</em> and <em>sample</em>, which returns a random item without removing it.
+///
+/// Every iterator walks the items in its own uniformly random order, independent of other iterators.
+///
+/// Enqueue takes constant amortized running time, dequeue and sample take constant worst-case
+/// running time: *O*(1). Creating an iterator takes *O*(n).
+///
+/// The randomness comes from the seedable `randomization::lcg_random::Lcg`. `new` seeds it with the
+/// time, while `with_seed` makes every order repeatable.
+///
+/// This implementation uses a Vec\<T\>, to hold its elements.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::fundamentals::randomized_queue::RandomizedQueue;
+///
+/// let mut queue = RandomizedQueue::with_seed(42);
+/// queue.enqueue("Bob");
+/// queue.enqueue("Alice");
+/// queue.enqueue("Eve");
+///
+/// let mut everyone: Vec<&str> = queue.iter().copied().collect();
+/// everyone.sort();
+/// assert_eq!(everyone, vec!["Alice", "Bob", "Eve"]);
+///
+/// let winner = queue.dequeue().unwrap();
+/// assert!(["Alice", "Bob", "Eve"].contains(&winner));
+/// assert_eq!(queue.size(), 2);
+/// ```
+pub struct RandomizedQueue<T> {
+    elements: Vec<T>,
+    rng: Cell<Lcg>,
+}
+
+impl<T> RandomizedQueue<T> {
+    /// Create a new randomized queue seeded with the current time.
+    pub fn new() -> RandomizedQueue<T> {
+        RandomizedQueue { elements: Vec::new(), rng: Cell::new(Lcg::from_time()) }
+    }
+
+    /// Create a new randomized queue whose random choices are determined by `seed`.
+    pub fn with_seed(seed: u64) -> RandomizedQueue<T> {
+        RandomizedQueue { elements: Vec::new(), rng: Cell::new(Lcg::new(seed)) }
+    }
+
+    fn uniform(&self, n: usize) -> usize {
+        let mut rng = self.rng.get();
+        let i = rng.uniform(n);
+        self.rng.set(rng);
+        i
+    }
+
+    /// Returns a new generator for an iterator, seeded from the queue's generator
+    fn fork(&self) -> Lcg {
+        let mut rng = self.rng.get();
+        let seed = rng.next_u32() as u64;
+        self.rng.set(rng);
+        Lcg::new(seed)
+    }
+
+    /// Adds an element to the queue.
+    pub fn enqueue(&mut self, val: T) {
+        self.elements.push(val);
+    }
+
+    /// Removes and returns an element chosen uniformly at random.
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.elements.is_empty() {
+            return None;
+        }
+        let i = self.uniform(self.elements.len());
+        Some(self.elements.swap_remove(i))
+    }
+
+    /// Returns an element chosen uniformly at random, without removing it.
+    pub fn sample(&self) -> Option<&T> {
+        if self.elements.is_empty() {
+            return None;
+        }
+        Some(&self.elements[self.uniform(self.elements.len())])
+    }
+
+    /// Checks if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns an iterator over the elements in a random order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut order: Vec<usize> = (0..self.elements.len()).collect();
+        self.fork().shuffle(&mut order);
+        Iter { elements: &self.elements, order: order.into_iter() }
+    }
+}
+
+impl<T> Default for RandomizedQueue<T> {
+    fn default() -> RandomizedQueue<T> {
+        RandomizedQueue::new()
+    }
+}
+
+/// An iterator over the elements of a `RandomizedQueue` in a random order, created by `RandomizedQueue::iter`.
+pub struct Iter<'a, T> {
+    elements: &'a [T],
+    order: vec::IntoIter<usize>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.order.next().map(|i| &self.elements[i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+delegate_iterator! {
+    /// An owning iterator over the elements of a `RandomizedQueue` in a random order, created by `into_iter`.
+    pub struct IntoIter<T>(vec::IntoIter<T>) -> T;
+}
+
+impl<T> IntoIterator for RandomizedQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        self.fork().shuffle(&mut self.elements);
+        IntoIter { inner: self.elements.into_iter() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RandomizedQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RandomizedQueue;
+
+    #[test]
+    fn test_dequeue_everything() {
+        let mut queue: RandomizedQueue<u32> = RandomizedQueue::with_seed(1);
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.sample(), None);
+        for i in 0..100 {
+            queue.enqueue(i);
+        }
+        let mut out = Vec::new();
+        while let Some(i) = queue.dequeue() {
+            out.push(i);
+        }
+        assert_ne!(out, (0..100).collect::<Vec<u32>>());
+        out.sort();
+        assert_eq!(out, (0..100).collect::<Vec<u32>>());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_same_seed_same_order() {
+        let mut a: RandomizedQueue<u32> = RandomizedQueue::with_seed(9);
+        let mut b: RandomizedQueue<u32> = RandomizedQueue::with_seed(9);
+        for i in 0..20 {
+            a.enqueue(i);
+            b.enqueue(i);
+        }
+        assert_eq!(a.iter().collect::<Vec<_>>(), b.iter().collect::<Vec<_>>());
+        assert_eq!(a.into_iter().collect::<Vec<_>>(), b.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_iterators_are_independent() {
+        let mut queue: RandomizedQueue<u32> = RandomizedQueue::with_seed(3);
+        for i in 0..20 {
+            queue.enqueue(i);
+        }
+        let first: Vec<&u32> = queue.iter().collect();
+        let second: Vec<&u32> = queue.iter().collect();
+        assert_ne!(first, second);
+        assert_eq!(queue.iter().len(), 20);
+    }
+
+    #[test]
+    fn test_sample_is_uniform() {
+        let mut queue: RandomizedQueue<usize> = RandomizedQueue::with_seed(5);
+        for i in 0..4 {
+            queue.enqueue(i);
+        }
+        let mut counts = [0; 4];
+        for _ in 0..40_000 {
+            counts[*queue.sample().unwrap()] += 1;
+        }
+        for count in counts {
+            assert!(count > 9_000 && count < 11_000, "Count was off: {}", count);
+        }
+        assert_eq!(queue.size(), 4);
+    }
+}
@@ -0,0 +1,212 @@
+#[derive(Debug)]
+/// The fundamentals resizing array queue module represents a first-in-first-out (FIFO) queue
+/// of generic items, with the same operations as `Queue`. Instead of a linked list it keeps its
+/// items in a circular buffer, so enqueue and dequeue do not allocate unless the buffer is resized.
+///
+/// The buffer doubles when it is full and halves when it is a quarter full, which keeps it
+/// between 25% and 100% full. Enqueue and dequeue take constant amortized running time,
+/// worst-case *O*(n) if a resize occurs. Peek, size, and is_empty take constant worst-case running time: *O*(1).
+///
+/// `with_capacity`, `capacity` and `shrink_to_fit` give explicit control over the buffer. A queue made with
+/// `with_capacity` never halves its buffer below that capacity, so a queue that keeps filling up and draining
+/// within it does not allocate at all. `shrink_to_fit` removes that floor.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::fundamentals::resizing_array_queue::ResizingArrayQueue;
+///
+/// let mut queue = ResizingArrayQueue::with_capacity(2);
+/// queue.enqueue("Bob");
+/// queue.enqueue("Alice");
+/// assert_eq!(queue.capacity(), 2);
+///
+/// // The buffer is full, so it doubles
+/// queue.enqueue("Eve");
+/// assert_eq!(queue.capacity(), 4);
+///
+/// assert_eq!(queue.dequeue(), Some("Bob"));
+/// queue.shrink_to_fit();
+/// assert_eq!(queue.capacity(), 2);
+/// assert_eq!(queue.peek(), Some(&"Alice"));
+/// ```
+pub struct ResizingArrayQueue<T> {
+    elements: Vec<Option<T>>,
+    first: usize,
+    n: usize,
+    min_capacity: usize,
+}
+
+impl<T> ResizingArrayQueue<T> {
+    /// Create a new empty queue without allocating.
+    pub fn new() -> ResizingArrayQueue<T> {
+        ResizingArrayQueue::with_capacity(0)
+    }
+
+    /// Create a new empty queue with room for `capacity` items before it resizes.
+    /// The buffer never shrinks below `capacity` on its own.
+    pub fn with_capacity(capacity: usize) -> ResizingArrayQueue<T> {
+        let mut elements = Vec::with_capacity(capacity);
+        elements.resize_with(capacity, || None);
+        ResizingArrayQueue { elements, first: 0, n: 0, min_capacity: capacity }
+    }
+
+    /// Returns the number of items the queue can hold before it resizes.
+    pub fn capacity(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Shrinks the buffer so that it holds exactly the items in the queue,
+    /// and lets it shrink below the capacity given to `with_capacity` from then on.
+    pub fn shrink_to_fit(&mut self) {
+        self.min_capacity = 0;
+        self.resize(self.n);
+    }
+
+    /// Moves the items to the front of a new buffer of the given capacity.
+    fn resize(&mut self, capacity: usize) {
+        let mut elements = Vec::with_capacity(capacity);
+        for i in 0..self.n {
+            let j = (self.first + i) % self.elements.len();
+            elements.push(self.elements[j].take());
+        }
+        elements.resize_with(capacity, || None);
+        self.elements = elements;
+        self.first = 0;
+    }
+
+    /// Adds an element to the back of the queue.
+    pub fn enqueue(&mut self, val: T) {
+        if self.n == self.elements.len() {
+            self.resize((2 * self.elements.len()).max(1));
+        }
+        let last = (self.first + self.n) % self.elements.len();
+        self.elements[last] = Some(val);
+        self.n += 1;
+    }
+
+    /// Removes an element from the front of the queue.
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        let val = self.elements[self.first].take();
+        self.first = (self.first + 1) % self.elements.len();
+        self.n -= 1;
+        if self.n > 0 && self.n == self.elements.len() / 4 && self.elements.len() / 2 >= self.min_capacity {
+            self.resize(self.elements.len() / 2);
+        }
+        val
+    }
+
+    /// Returns the value at the front of the queue.
+    pub fn peek(&self) -> Option<&T> {
+        if self.n == 0 {
+            return None;
+        }
+        self.elements[self.first].as_ref()
+    }
+
+    /// Checks if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the number of items in the queue.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+}
+
+impl<T> Default for ResizingArrayQueue<T> {
+    fn default() -> ResizingArrayQueue<T> {
+        ResizingArrayQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResizingArrayQueue;
+
+    #[test]
+    fn test_fifo_order_across_wrap_around() {
+        let mut queue: ResizingArrayQueue<u32> = ResizingArrayQueue::with_capacity(4);
+        queue.enqueue(1);
+        queue.enqueue(2);
+        queue.enqueue(3);
+        assert_eq!(queue.dequeue(), Some(1));
+        assert_eq!(queue.dequeue(), Some(2));
+        // These wrap around the end of the buffer
+        queue.enqueue(4);
+        queue.enqueue(5);
+        queue.enqueue(6);
+        assert_eq!(queue.capacity(), 4);
+        // And this one resizes while the queue is wrapped
+        queue.enqueue(7);
+        assert_eq!(queue.capacity(), 8);
+        for expected in 3..=7 {
+            assert_eq!(queue.dequeue(), Some(expected));
+        }
+        assert_eq!(queue.dequeue(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_grows_and_shrinks() {
+        let mut queue: ResizingArrayQueue<usize> = ResizingArrayQueue::new();
+        assert_eq!(queue.capacity(), 0);
+        for i in 0..16 {
+            queue.enqueue(i);
+        }
+        assert_eq!(queue.capacity(), 16);
+        for _ in 0..12 {
+            queue.dequeue();
+        }
+        // 4 of 16 left, a quarter full
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.peek(), Some(&12));
+        queue.dequeue();
+        queue.dequeue();
+        assert_eq!(queue.capacity(), 4);
+        assert_eq!(queue.size(), 2);
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut queue: ResizingArrayQueue<u8> = ResizingArrayQueue::with_capacity(100);
+        queue.enqueue(20);
+        queue.enqueue(30);
+        queue.shrink_to_fit();
+        assert_eq!(queue.capacity(), 2);
+        assert_eq!(queue.dequeue(), Some(20));
+        queue.dequeue();
+        queue.shrink_to_fit();
+        assert_eq!(queue.capacity(), 0);
+        queue.enqueue(40);
+        assert_eq!(queue.peek(), Some(&40));
+    }
+
+    #[test]
+    fn test_keeps_the_initial_capacity() {
+        let mut queue: ResizingArrayQueue<usize> = ResizingArrayQueue::with_capacity(8);
+        for round in 0..3 {
+            for i in 0..8 {
+                queue.enqueue(round * 8 + i);
+            }
+            while queue.size() > 1 {
+                queue.dequeue();
+            }
+            assert_eq!(queue.capacity(), 8);
+            queue.dequeue();
+        }
+        // Growing past the initial capacity can shrink back down to it, but not below
+        for i in 0..32 {
+            queue.enqueue(i);
+        }
+        while queue.size() > 1 {
+            queue.dequeue();
+        }
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.dequeue(), Some(31));
+    }
+}
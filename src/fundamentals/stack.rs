@@ -1,3 +1,7 @@
+use std::{iter::Rev, slice, vec};
+
+use super::iter::delegate_iterator;
+
 #[derive(Debug)]
 /// The fundamentals stack module represents a last-in-first-out (LIFO) stack
 /// of generic items. It supports <em>push</em> and <em>pop</em> operations,
@@ -64,6 +68,23 @@ impl<T> Stack<T> {
         }
     }
 
+    /// Create a new stack with room for `capacity` elements before it resizes.
+    pub fn with_capacity(capacity: usize) -> Stack<T> {
+        Stack {
+            elements: Vec::<T>::with_capacity(capacity),
+        }
+    }
+
+    /// Return the number of elements the stack can hold before it resizes.
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity()
+    }
+
+    /// Shrink the capacity of the stack as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.elements.shrink_to_fit();
+    }
+
     /// Push an element to the stack.
     pub fn push(&mut self, val: T) {
         self.elements.push(val);
@@ -96,10 +117,109 @@ impl<T> Default for Stack<T> {
     }
 }
 
+impl<T> Stack<T> {
+    /// Returns an iterator over the elements from the top to the bottom (LIFO).
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { inner: self.elements.iter().rev() }
+    }
+
+    /// Returns an iterator that allows modifying each element from the top to the bottom (LIFO).
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { inner: self.elements.iter_mut().rev() }
+    }
+}
+
+delegate_iterator! {
+    /// An iterator over the elements of a `Stack`, created by `Stack::iter`.
+    pub struct Iter<'a, T>(Rev<slice::Iter<'a, T>>) -> &'a T;
+}
+
+delegate_iterator! {
+    /// A mutable iterator over the elements of a `Stack`, created by `Stack::iter_mut`.
+    pub struct IterMut<'a, T>(Rev<slice::IterMut<'a, T>>) -> &'a mut T;
+}
+
+delegate_iterator! {
+    /// An owning iterator over the elements of a `Stack`, created by `into_iter`.
+    pub struct IntoIter<T>(Rev<vec::IntoIter<T>>) -> T;
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { inner: self.elements.into_iter().rev() }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Stack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Stack<T> {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+/// Serializes the stack as a sequence from the bottom to the top
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Stack<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.elements)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Stack<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Stack { elements: Vec::deserialize(deserializer)? })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Stack;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut stack: Stack<u8> = Stack::new();
+        stack.push(20);
+        stack.push(30);
+        let json = serde_json::to_string(&stack).unwrap();
+        assert_eq!(json, "[20,30]");
+
+        let mut back: Stack<u8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.pop(), Some(30));
+        assert_eq!(back.pop(), Some(20));
+    }
+
     #[test]
     fn test_push() {
         let mut stack: Stack<u8> = Stack::new();
@@ -135,6 +255,30 @@ mod tests {
         assert_eq!(stack.size(), 2);
     }
 
+    #[test]
+    fn test_capacity() {
+        let mut stack: Stack<u8> = Stack::with_capacity(10);
+        assert!(stack.capacity() >= 10);
+        stack.push(20);
+        stack.shrink_to_fit();
+        assert!(stack.capacity() >= 1 && stack.capacity() < 10);
+        assert_eq!(stack.pop(), Some(20));
+    }
+
+    #[test]
+    fn test_iter_is_lifo() {
+        let mut stack: Stack<u8> = (1..=3).collect();
+        assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        for val in stack.iter_mut() {
+            *val *= 10;
+        }
+        stack.extend(vec![40, 50]);
+        assert_eq!(stack.peek(), Some(&50));
+        assert_eq!(stack.iter().len(), 5);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![50, 40, 30, 20, 10]);
+    }
+
     #[test]
     fn test_is_empty() {
         let mut stack: Stack<u8> = Stack::new();
@@ -1,3 +1,7 @@
+use std::{collections::HashMap, error::Error, fmt, hash::Hash, io::{self, Write}};
+
+use crate::io::input::{In, InputError};
+
 /// The Quick Union fundamentals module represents a union find structure
 /// which allows for checking dynamic connectivity. This specific implementation uses
 /// a Non-Weighted Quick Union without path compression.
@@ -162,6 +166,484 @@ impl WeightedQuickUnionUF {
 
         self.count -= 1;
     }
+
+    /// Reads a union find in the algs4 format: the number of elements followed by the pairs
+    /// to union until the end of the input, e.g. `tinyUF.txt`.
+    /// An element outside the union find is an error rather than a panic.
+    pub fn from_reader(input: &mut In) -> Result<WeightedQuickUnionUF, InputError> {
+        let mut uf = WeightedQuickUnionUF::new(input.read()?);
+        let n = uf.id.len();
+        while !input.is_empty() {
+            let p = input.read_index(n)?;
+            let q = input.read_index(n)?;
+            uf.union(p, q);
+        }
+        Ok(uf)
+    }
+
+    /// Writes the union find in the format read by `from_reader`. The pairs that were unioned
+    /// are not stored, so one pair per link to a parent is written instead, which gives
+    /// the same components when read back.
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{}", self.id.len())?;
+        for (p, parent) in self.id.iter().enumerate() {
+            if p != *parent {
+                writeln!(out, "{} {}", p, parent)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The Weighted Quick Union Path Compression fundamentals module represents a union find structure
+/// which allows for checking dynamic connectivity. This specific implementation uses
+/// a Weighted Quick Union (union by size) with path compression by halving.
+/// 
+/// Every time `find` walks up the tree it points each node it visits to its grandparent,
+/// which roughly halves the length of the path. Together with union by size this gives
+/// an amortized time per operation of *O*(α(n)), where α is the inverse Ackermann function,
+/// which is less than 5 for any practical n. Since find changes the tree, it takes `&mut self`.
+/// 
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::uf::WeightedQuickUnionPathCompressionUF;
+/// 
+/// let mut our_qu = WeightedQuickUnionPathCompressionUF::new(5);
+/// our_qu.union(0, 2);
+/// our_qu.union(2, 4);
+/// 
+/// assert!(our_qu.connected(0, 4));
+/// assert!(!our_qu.connected(1, 4));
+/// assert_eq!(our_qu.size_of(4), 3);
+/// assert_eq!(our_qu.count(), 3);
+/// ``` 
+pub struct WeightedQuickUnionPathCompressionUF {
+    count: usize,
+    size: Vec<usize>,
+    id: Vec<usize>
+}
+
+impl WeightedQuickUnionPathCompressionUF {
+    /// Create a new WeightedQuickUnionPathCompressionUF with N elements were all elements are singletons
+    pub fn new(n: usize) -> WeightedQuickUnionPathCompressionUF {
+        WeightedQuickUnionPathCompressionUF { count: n, size: vec![1; n], id: (0..n).collect() }
+    }
+
+    /// Finds the root of a node, halving the path on the way
+    pub fn find(&mut self, mut p: usize) -> usize {
+        while p != self.id[p] {
+            self.id[p] = self.id[self.id[p]];
+            p = self.id[p];
+        }
+        p
+    }
+
+    /// Checks if two nodes are connected by comparing their roots
+    pub fn connected(&mut self, p: usize, q: usize) -> bool {
+        self.find(p) == self.find(q)
+    }
+
+    /// Returns the count 
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of elements in the component containing p
+    pub fn size_of(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
+
+    /// Unions two elements together, the root of the smaller tree is linked to the root of the larger
+    pub fn union(&mut self, p: usize, q: usize) {
+        let root_p = self.find(p);
+        let root_q = self.find(q);
+
+        if root_p == root_q {
+            return;
+        }
+
+        let (smallest, largest) = if self.size[root_p] < self.size[root_q] {
+            (root_p, root_q)
+        } else {
+            (root_q, root_p)
+        };
+
+        self.id[smallest] = largest;
+        self.size[largest] += self.size[smallest];
+
+        self.count -= 1;
+    }
+}
+
+/// The Rollback fundamentals module represents a union find structure whose unions can be undone.
+/// This specific implementation uses a Weighted Quick Union (union by size) without path compression,
+/// since compressing a path would change the tree in ways that cannot cheaply be undone.
+/// 
+/// Every successful union is recorded on a history stack. `snapshot` returns the current height
+/// of that stack, and `rollback_to` undoes unions, newest first, until the stack is back at that height.
+/// Find and union take logarithmic time: *O*(log n), undoing a union takes constant time: *O*(1).
+/// This is the building block of the offline dynamic connectivity in `graph::dynamic_connectivity`.
+/// 
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::uf::RollbackUF;
+/// 
+/// let mut our_qu = RollbackUF::new(5);
+/// our_qu.union(0, 2);
+/// let snapshot = our_qu.snapshot();
+/// our_qu.union(2, 4);
+/// assert!(our_qu.connected(0, 4));
+/// 
+/// our_qu.rollback_to(snapshot);
+/// assert!(!our_qu.connected(0, 4));
+/// assert!(our_qu.connected(0, 2));
+/// assert_eq!(our_qu.count(), 4);
+/// ``` 
+pub struct RollbackUF {
+    count: usize,
+    size: Vec<usize>,
+    id: Vec<usize>,
+    history: Vec<usize>,
+}
+
+impl RollbackUF {
+    /// Create a new RollbackUF with N elements were all elements are singletons
+    pub fn new(n: usize) -> RollbackUF {
+        RollbackUF { count: n, size: vec![1; n], id: (0..n).collect(), history: Vec::new() }
+    }
+
+    /// Finds the root of a node 
+    pub fn find(&self, mut p: usize) -> usize {
+        while p != self.id[p] {
+            p = self.id[p];
+        }
+        p
+    }
+
+    /// Checks if two nodes are connected by comparing their roots
+    pub fn connected(&self, p: usize, q: usize) -> bool {
+        self.find(p) == self.find(q)
+    }
+
+    /// Returns the count 
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Unions two elements together, returns false if they were already connected.
+    /// Only unions that return true are recorded in the history.
+    pub fn union(&mut self, p: usize, q: usize) -> bool {
+        let root_p = self.find(p);
+        let root_q = self.find(q);
+
+        if root_p == root_q {
+            return false;
+        }
+
+        let (smallest, largest) = if self.size[root_p] < self.size[root_q] {
+            (root_p, root_q)
+        } else {
+            (root_q, root_p)
+        };
+
+        self.id[smallest] = largest;
+        self.size[largest] += self.size[smallest];
+        self.history.push(smallest);
+
+        self.count -= 1;
+        true
+    }
+
+    /// Returns the number of unions made so far, to be given to `rollback_to`
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes every union made after `snapshot` was taken.
+    /// Panics if the snapshot is newer than the current history.
+    pub fn rollback_to(&mut self, snapshot: usize) {
+        if snapshot > self.history.len() {
+            panic!("Cannot roll back to a snapshot that has already been rolled back!")
+        }
+        while self.history.len() > snapshot {
+            let smallest = self.history.pop().unwrap();
+            let largest = self.id[smallest];
+            self.size[largest] -= self.size[smallest];
+            self.id[smallest] = smallest;
+            self.count += 1;
+        }
+    }
+}
+
+/// The Weighted Potential fundamentals module represents a union find structure where every element
+/// has a potential, an unknown number x_p, and every union records a constraint x_q - x_p = w.
+/// When p and q are connected, `diff` returns the difference x_q - x_p implied by the constraints.
+/// 
+/// A union between elements that are already connected is only accepted when it agrees with
+/// the difference that is already known, otherwise it returns a `Contradiction` and changes nothing.
+/// 
+/// Each element stores its potential relative to its parent. This specific implementation uses a
+/// Weighted Quick Union (union by size) with path halving, adding up the relative potentials
+/// of the skipped links, so every operation takes amortized *O*(α(n)) time.
+/// 
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::uf::WeightedPotentialUF;
+/// 
+/// let mut heights = WeightedPotentialUF::new(4);
+/// // 1 is 3 higher than 0, 2 is 4 higher than 1
+/// heights.union(0, 1, 3).unwrap();
+/// heights.union(1, 2, 4).unwrap();
+/// assert_eq!(heights.diff(0, 2), Some(7));
+/// assert_eq!(heights.diff(2, 0), Some(-7));
+/// assert_eq!(heights.diff(0, 3), None);
+/// 
+/// // Agrees with what is known
+/// assert!(heights.union(2, 0, -7).is_ok());
+/// // Does not
+/// let err = heights.union(0, 2, 5).unwrap_err();
+/// assert_eq!(err.known, 7);
+/// ``` 
+pub struct WeightedPotentialUF {
+    count: usize,
+    size: Vec<usize>,
+    id: Vec<usize>,
+    potential: Vec<i64>,
+}
+
+/// A union that disagrees with the difference already implied between two connected elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    pub p: usize,
+    pub q: usize,
+    /// The difference x_q - x_p that is already known
+    pub known: i64,
+    /// The difference x_q - x_p the union asked for
+    pub given: i64,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x[{}] - x[{}] is already {}, cannot also be {}", self.q, self.p, self.known, self.given)
+    }
+}
+
+impl Error for Contradiction {}
+
+impl WeightedPotentialUF {
+    /// Create a new WeightedPotentialUF with N elements were all elements are singletons
+    pub fn new(n: usize) -> WeightedPotentialUF {
+        WeightedPotentialUF { count: n, size: vec![1; n], id: (0..n).collect(), potential: vec![0; n] }
+    }
+
+    /// Finds the root of a node and the potential of the node relative to the root, halving the path on the way
+    pub fn find(&mut self, mut p: usize) -> (usize, i64) {
+        let mut total = 0;
+        while p != self.id[p] {
+            let parent = self.id[p];
+            self.potential[p] += self.potential[parent];
+            self.id[p] = self.id[parent];
+            total += self.potential[p];
+            p = self.id[p];
+        }
+        (p, total)
+    }
+
+    /// Checks if two nodes are connected by comparing their roots
+    pub fn connected(&mut self, p: usize, q: usize) -> bool {
+        self.find(p).0 == self.find(q).0
+    }
+
+    /// Returns x_q - x_p if p and q are connected
+    pub fn diff(&mut self, p: usize, q: usize) -> Option<i64> {
+        let (root_p, pot_p) = self.find(p);
+        let (root_q, pot_q) = self.find(q);
+        if root_p != root_q {
+            return None;
+        }
+        Some(pot_q - pot_p)
+    }
+
+    /// Returns the count 
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Records x_q - x_p = w, connecting p and q.
+    /// If they are already connected with another difference, nothing changes and the contradiction is returned.
+    pub fn union(&mut self, p: usize, q: usize, w: i64) -> Result<(), Contradiction> {
+        let (root_p, pot_p) = self.find(p);
+        let (root_q, pot_q) = self.find(q);
+
+        if root_p == root_q {
+            if pot_q - pot_p != w {
+                return Err(Contradiction { p, q, known: pot_q - pot_p, given: w });
+            }
+            return Ok(());
+        }
+
+        // x_root_q - x_root_p
+        let between = w - pot_q + pot_p;
+        if self.size[root_p] < self.size[root_q] {
+            self.id[root_p] = root_q;
+            self.potential[root_p] = -between;
+            self.size[root_q] += self.size[root_p];
+        } else {
+            self.id[root_q] = root_p;
+            self.potential[root_q] = between;
+            self.size[root_p] += self.size[root_q];
+        }
+
+        self.count -= 1;
+        Ok(())
+    }
+}
+
+/// The Keyed fundamentals module represents a union find structure over any hashable elements,
+/// instead of the dense IDs `0..n` the other union finds need up front.
+/// 
+/// Every new element gets the next free ID when it first appears, either through `make_set`
+/// or through `union`. The IDs are kept in a HashMap, the forest itself is a Weighted Quick Union
+/// with path halving, so every operation takes amortized *O*(α(n)) time plus the hashing.
+/// 
+/// Every element is kept twice, once in the list of elements and once as a key of the map, so it has to be `Clone`.
+/// The structure is `Send` and `Sync` whenever the elements are.
+/// 
+/// # Examples
+/// ```
+/// use itualgs_rs::fundamentals::uf::KeyedUF;
+/// 
+/// let mut friends = KeyedUF::new();
+/// friends.union("Alice".to_string(), "Bob".to_string());
+/// friends.union("Bob".to_string(), "Eve".to_string());
+/// friends.make_set("Mallory".to_string());
+/// 
+/// assert!(friends.connected(&"Alice".to_string(), &"Eve".to_string()));
+/// assert!(!friends.connected(&"Alice".to_string(), &"Mallory".to_string()));
+/// assert_eq!(friends.count(), 2);
+/// 
+/// let mut groups: Vec<usize> = friends.components().map(|group| group.len()).collect();
+/// groups.sort();
+/// assert_eq!(groups, vec![1, 3]);
+/// ``` 
+pub struct KeyedUF<T: Hash + Eq + Clone> {
+    count: usize,
+    size: Vec<usize>,
+    id: Vec<usize>,
+    elements: Vec<T>,
+    index: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq + Clone> KeyedUF<T> {
+    /// Create a new empty KeyedUF
+    pub fn new() -> KeyedUF<T> {
+        KeyedUF { count: 0, size: Vec::new(), id: Vec::new(), elements: Vec::new(), index: HashMap::new() }
+    }
+
+    /// Returns the ID of an element, giving it the next free one if it is new
+    fn id_of(&mut self, x: T) -> usize {
+        if let Some(&i) = self.index.get(&x) {
+            return i;
+        }
+        let i = self.elements.len();
+        self.elements.push(x.clone());
+        self.index.insert(x, i);
+        self.id.push(i);
+        self.size.push(1);
+        self.count += 1;
+        i
+    }
+
+    fn root(&mut self, mut p: usize) -> usize {
+        while p != self.id[p] {
+            self.id[p] = self.id[self.id[p]];
+            p = self.id[p];
+        }
+        p
+    }
+
+    /// Adds an element as a singleton, returns false if it was already known
+    pub fn make_set(&mut self, x: T) -> bool {
+        let n = self.elements.len();
+        self.id_of(x) == n
+    }
+
+    /// Checks if an element has been seen
+    pub fn contains(&self, x: &T) -> bool {
+        self.index.contains_key(x)
+    }
+
+    /// Finds the representative of the component containing x, or None if x has not been seen
+    pub fn find(&mut self, x: &T) -> Option<&T> {
+        let p = *self.index.get(x)?;
+        let root = self.root(p);
+        Some(&self.elements[root])
+    }
+
+    /// Checks if two elements are connected, elements that have not been seen are not connected to anything
+    pub fn connected(&mut self, x: &T, y: &T) -> bool {
+        match (self.index.get(x), self.index.get(y)) {
+            (Some(&p), Some(&q)) => self.root(p) == self.root(q),
+            _ => false,
+        }
+    }
+
+    /// Returns the count of components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of elements
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Unions two elements together, adding them first if they are new
+    pub fn union(&mut self, x: T, y: T) {
+        let p = self.id_of(x);
+        let q = self.id_of(y);
+        let root_p = self.root(p);
+        let root_q = self.root(q);
+
+        if root_p == root_q {
+            return;
+        }
+
+        let (smallest, largest) = if self.size[root_p] < self.size[root_q] {
+            (root_p, root_q)
+        } else {
+            (root_q, root_p)
+        };
+
+        self.id[smallest] = largest;
+        self.size[largest] += self.size[smallest];
+
+        self.count -= 1;
+    }
+
+    /// Returns an iterator over the components, each one a list of its elements.
+    /// Components come in the order their first element was seen, and so do the elements within them.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<&T>> {
+        let mut group_of: Vec<Option<usize>> = vec![None; self.elements.len()];
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for p in 0..self.elements.len() {
+            let root = self.root(p);
+            let g = *group_of[root].get_or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[g].push(p);
+        }
+        let elements = &self.elements;
+        groups.into_iter().map(move |group| group.into_iter().map(|p| &elements[p]).collect())
+    }
+}
+
+impl<T: Hash + Eq + Clone> Default for KeyedUF<T> {
+    fn default() -> KeyedUF<T> {
+        KeyedUF::new()
+    }
 }
 
 /// The Quick Find fundamentals module represents a union find structure
@@ -327,4 +809,207 @@ mod tests {
         assert!(qu.connected(7, 2) == true);
         assert!(qu.connected(6, 6) == true);
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn tiny_uf_from_reader_wqu() {
+        use crate::io::input::{In, InputErrorKind};
+
+        let text = "10\n4 3\n3 8\n6 5\n9 4\n2 1\n8 9\n5 0\n7 2\n6 1\n1 0\n6 7\n";
+        let qu = WeightedQuickUnionUF::from_reader(&mut In::from_text(text)).unwrap();
+        assert_eq!(qu.count(), 2);
+        assert!(qu.connected(1, 6));
+        assert!(!qu.connected(3, 7));
+
+        let mut out = Vec::new();
+        qu.write_to(&mut out).unwrap();
+        let again = WeightedQuickUnionUF::from_reader(&mut In::from_text(std::str::from_utf8(&out).unwrap())).unwrap();
+        assert_eq!(again.count(), 2);
+        for p in 0..10 {
+            for q in 0..10 {
+                assert_eq!(again.connected(p, q), qu.connected(p, q));
+            }
+        }
+
+        let err = WeightedQuickUnionUF::from_reader(&mut In::from_text("3\n0 1\n2")).err().unwrap();
+        assert_eq!(err.kind, InputErrorKind::UnexpectedEnd);
+        let err = WeightedQuickUnionUF::from_reader(&mut In::from_text("3\n0 3")).err().unwrap();
+        assert_eq!(err.kind, InputErrorKind::OutOfRange { value: 3, bound: 3 });
+    }
+
+    /* Tests for Weighted Quick Union with Path Compression */
+    use super::WeightedQuickUnionPathCompressionUF;
+
+    #[test]
+    pub fn tiny_uf_sedgewick_wqupc() {
+        let mut qu = WeightedQuickUnionPathCompressionUF::new(10);
+        qu.union(4, 3);
+        qu.union(3, 8);
+        qu.union(6, 5);
+        qu.union(9, 4);
+        qu.union(2, 1);
+        qu.union(8, 9);
+        qu.union(5, 0);
+        qu.union(7, 2);
+        qu.union(6, 1);
+        qu.union(1, 0);
+        qu.union(6, 7);
+
+        assert!(qu.connected(1, 6));
+        assert!(qu.connected(7, 2));
+        assert!(!qu.connected(4, 0));
+        assert_eq!(qu.count(), 2);
+        assert_eq!(qu.size_of(3), 4);
+        assert_eq!(qu.size_of(7), 6);
+    }
+
+    #[test]
+    pub fn path_halving_flattens_chain() {
+        let n = 1 << 10;
+        let mut qu = WeightedQuickUnionPathCompressionUF::new(n);
+        // Union pairs, then pairs of pairs, ... which builds the tallest tree union by size allows
+        let mut step = 1;
+        while step < n {
+            for i in (0..n).step_by(2 * step) {
+                qu.union(i, i + step);
+            }
+            step *= 2;
+        }
+        assert_eq!(qu.count(), 1);
+        assert_eq!(qu.size_of(0), n);
+        let root = qu.find(0);
+        for p in 0..n {
+            assert_eq!(qu.find(p), root);
+        }
+        // After a find from every node no path is longer than one step
+        assert!((0..n).all(|p| qu.id[qu.id[p]] == qu.id[p]));
+    }
+
+    /* Tests for Rollback */
+    use super::RollbackUF;
+
+    #[test]
+    pub fn nested_rollbacks() {
+        let mut qu = RollbackUF::new(6);
+        let empty = qu.snapshot();
+        qu.union(0, 1);
+        qu.union(2, 3);
+        let two = qu.snapshot();
+        assert!(qu.union(1, 3));
+        assert!(!qu.union(0, 2));
+        qu.union(4, 5);
+        assert_eq!(qu.count(), 2);
+
+        qu.rollback_to(two);
+        assert_eq!(qu.count(), 4);
+        assert!(qu.connected(2, 3));
+        assert!(!qu.connected(0, 3));
+        assert!(!qu.connected(4, 5));
+
+        // The history can grow again after a rollback
+        qu.union(0, 5);
+        assert!(qu.connected(1, 5));
+        qu.rollback_to(empty);
+        assert_eq!(qu.count(), 6);
+        assert!((0..6).all(|p| qu.find(p) == p));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn rollback_to_future() {
+        let mut qu = RollbackUF::new(3);
+        qu.union(0, 1);
+        let snapshot = qu.snapshot();
+        qu.rollback_to(0);
+        qu.rollback_to(snapshot);
+    }
+
+    /* Tests for Keyed */
+    use super::KeyedUF;
+
+    #[test]
+    pub fn keyed_matches_dense_ids() {
+        let mut keyed = KeyedUF::new();
+        let mut qu = WeightedQuickUnionUF::new(10);
+        let name = |i: usize| format!("v{}", i);
+        for (p, q) in [(4, 3), (3, 8), (6, 5), (9, 4), (2, 1), (8, 9), (5, 0), (7, 2), (6, 1)] {
+            keyed.union(name(p), name(q));
+            qu.union(p, q);
+        }
+        assert_eq!(keyed.size(), 10);
+        assert_eq!(keyed.count(), qu.count());
+        for p in 0..10 {
+            for q in 0..10 {
+                assert_eq!(keyed.connected(&name(p), &name(q)), qu.connected(p, q));
+            }
+        }
+        let root = keyed.find(&name(3)).cloned().unwrap();
+        assert_eq!(keyed.find(&name(9)), Some(&root));
+        assert_eq!(keyed.find(&name(42)), None);
+    }
+
+    #[test]
+    pub fn keyed_components() {
+        let mut keyed = KeyedUF::new();
+        assert!(keyed.make_set('a'));
+        assert!(!keyed.make_set('a'));
+        keyed.union('b', 'c');
+        keyed.union('d', 'b');
+        keyed.make_set('e');
+        assert!(!keyed.connected(&'a', &'z'));
+        assert!(!keyed.contains(&'z'));
+        let groups: Vec<Vec<&char>> = keyed.components().collect();
+        assert_eq!(groups, vec![vec![&'a'], vec![&'b', &'c', &'d'], vec![&'e']]);
+    }
+
+    #[test]
+    pub fn keyed_can_move_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<KeyedUF<String>>();
+
+        let mut keyed = KeyedUF::new();
+        keyed.union("a".to_string(), "b".to_string());
+        let mut keyed = std::thread::spawn(move || {
+            keyed.union("b".to_string(), "c".to_string());
+            keyed
+        }).join().unwrap();
+        assert!(keyed.connected(&"a".to_string(), &"c".to_string()));
+    }
+
+    /* Tests for Weighted Potential */
+    use super::{Contradiction, WeightedPotentialUF};
+    use crate::randomization::lcg_random::Lcg;
+
+    #[test]
+    pub fn potential_matches_hidden_values() {
+        let mut rng = Lcg::new(21);
+        let n = 300;
+        let x: Vec<i64> = (0..n).map(|_| rng.uniform(1000) as i64 - 500).collect();
+        let mut qu = WeightedPotentialUF::new(n);
+        for _ in 0..2 * n {
+            let p = rng.uniform(n);
+            let q = rng.uniform(n);
+            assert!(qu.union(p, q, x[q] - x[p]).is_ok());
+        }
+        for _ in 0..1000 {
+            let p = rng.uniform(n);
+            let q = rng.uniform(n);
+            match qu.diff(p, q) {
+                Some(d) => assert_eq!(d, x[q] - x[p]),
+                None => assert!(!qu.connected(p, q)),
+            }
+        }
+        assert_eq!(qu.diff(5, 5), Some(0));
+    }
+
+    #[test]
+    pub fn contradiction_changes_nothing() {
+        let mut qu = WeightedPotentialUF::new(3);
+        qu.union(0, 1, 2).unwrap();
+        qu.union(1, 2, 2).unwrap();
+        assert_eq!(qu.union(2, 0, 1), Err(Contradiction { p: 2, q: 0, known: -4, given: 1 }));
+        assert_eq!(qu.union(1, 1, 1).unwrap_err().known, 0);
+        assert_eq!(qu.diff(0, 2), Some(4));
+        assert_eq!(qu.count(), 1);
+        assert_eq!(format!("{}", qu.union(0, 2, 3).unwrap_err()), "x[2] - x[0] is already 4, cannot also be 3");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{sorting::index_min_pq::IndexMinPQ, fundamentals::stack::Stack};
+use crate::{sorting::{index_dary_pq::IndexDaryPQ, index_min_pq::IndexMinPQ}, fundamentals::stack::Stack};
 
 use super::{edge::Edge, edge_weighted_graph::EdgeWeightedGraph, graph};
 
@@ -7,7 +7,9 @@ use super::{edge::Edge, edge_weighted_graph::EdgeWeightedGraph, graph};
 /// Dijkstras algorithm is for findin the shortest path between two points. 
 /// This is done by creating a shortest path tree which means, that we actually gets the shortest path to any point in the edgeweighted graph.
 /// 
-/// The `new` method runs in time *O(E log V)* for a graph with edges *E* and vertices *V*.
+/// The `new` method runs in time *O(E log V)* for a graph with edges *E* and vertices *V*, on an `IndexMinPQ`.
+/// `with_arity` runs on an `IndexDaryPQ` instead, a d-ary heap, which makes each relaxation cheaper
+/// and is faster on dense graphs, where *E* is much larger than *V*. Both give the same distances.
 /// 
 /// Author: AlberRossJoh
 /// 
@@ -37,10 +39,23 @@ use super::{edge::Edge, edge_weighted_graph::EdgeWeightedGraph, graph};
 /// assert_eq!(path.pop().unwrap().weight, 2);
 /// assert_eq!(path.is_empty(), true);
 /// ```
+///
+/// ```
+/// use itualgs_rs::graph::dijkstra_sp::DijkstraSP;
+/// use itualgs_rs::graph::edge::Edge;
+/// use itualgs_rs::graph::edge_weighted_graph::EdgeWeightedGraph;
+///
+/// let mut g = EdgeWeightedGraph::new(3);
+/// g.add_edge(Edge::new(0, 1, 4));
+/// g.add_edge(Edge::new(1, 2, 1));
+/// g.add_edge(Edge::new(0, 2, 7));
+///
+/// let sp = DijkstraSP::with_arity::<4>(g, 0);
+/// assert_eq!(sp.get_distance_to(&2), 5);
+/// ```
 pub struct DijkstraSP {
     dist_to: Vec<u128>,
     edge_to: Vec<Option<Edge>>,
-    pq: IndexMinPQ<u128>,
 }
 
 impl DijkstraSP {
@@ -50,15 +65,47 @@ impl DijkstraSP {
         let edge_to:Vec<Option<Edge>> = vec![None; G.V];
 
         dist_to[s] = 0;
-        let pq = IndexMinPQ::<u128>::new(G.V);
+        let mut pq = IndexMinPQ::<u128>::new(G.V);
 
-        let mut tmp = DijkstraSP{dist_to: dist_to, edge_to: edge_to, pq:pq};
-        tmp.pq.insert(&s, tmp.dist_to[s]);
+        let mut tmp = DijkstraSP{dist_to, edge_to};
+        pq.insert(&s, tmp.dist_to[s]);
 
-        while !tmp.pq.is_empty() {
-            let v = tmp.pq.delete_min();
+        while !pq.is_empty() {
+            let v = pq.delete_min();
             for e in G.adj(&v){
-                tmp.relax(e, &v);
+                if let Some(w) = tmp.relax(e, &v) {
+                    if pq.contains(w) {
+                        pq.decrease_key(&w, tmp.dist_to[w]);
+                    } else {
+                        pq.insert(&w, tmp.dist_to[w]);
+                    }
+                }
+            }
+        }
+        tmp
+    }
+
+    /// Runs Dijkstra with an indexed priority queue where every node has `D` children.
+    pub fn with_arity<const D: usize>(g: EdgeWeightedGraph, s: usize) -> Self{
+        let mut dist_to = vec![u128::MAX;g.V];
+        let edge_to:Vec<Option<Edge>> = vec![None; g.V];
+
+        dist_to[s] = 0;
+        let mut pq = IndexDaryPQ::<u128, D>::new(g.V);
+
+        let mut tmp = DijkstraSP{dist_to, edge_to};
+        pq.insert(&s, tmp.dist_to[s]);
+
+        while !pq.is_empty() {
+            let v = pq.delete_min();
+            for e in g.adj(&v){
+                if let Some(w) = tmp.relax(e, &v) {
+                    if pq.contains(w) {
+                        pq.decrease_key(&w, tmp.dist_to[w]);
+                    } else {
+                        pq.insert(&w, tmp.dist_to[w]);
+                    }
+                }
             }
         }
         tmp
@@ -93,25 +140,23 @@ impl DijkstraSP {
     }
 
 
-    fn relax(&mut self, e:&Edge, v: &usize){
+    /// Relaxes `e` from `v`, and returns the other end of `e` if its distance went down
+    /// so the caller can update its priority queue.
+    fn relax(&mut self, e:&Edge, v: &usize) -> Option<usize>{
         let w = e.other(*v);
         let tmp = &mut self.dist_to;
         if tmp[*w] > tmp[*v]+e.weight {
             tmp[*w] = tmp[*v]+e.weight;
             self.edge_to[*w] = Some(e.clone());
-            if self.pq.contains(*w) {
-                self.pq.decrease_key(w, self.dist_to[*w]);
-            } else {
-                self.pq.insert(w, self.dist_to[*w]);
-            }
+            return Some(*w);
         }
-        
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::graph::edge::Edge;
+    use crate::{graph::edge::Edge, randomization::lcg_random::Lcg};
 
     use super::EdgeWeightedGraph;
 
@@ -138,4 +183,26 @@ mod tests {
         assert_eq!(path.pop().unwrap().weight, 2);
         assert_eq!(path.is_empty(), true);
     }
+
+    #[test]
+    fn test_new_and_with_arity_agree(){
+        let mut rng = Lcg::new(7);
+        let v = 60;
+        let edges: Vec<(usize, usize, u128)> = (0..400)
+            .map(|_| (rng.uniform(v), rng.uniform(v), rng.uniform(100) as u128))
+            .collect();
+        let graph = || {
+            let mut g = EdgeWeightedGraph::new(v);
+            for (a, b, w) in &edges {
+                g.add_edge(Edge::new(*a, *b, *w));
+            }
+            g
+        };
+        let binary = DijkstraSP::new(graph(), 0);
+        let dary = DijkstraSP::with_arity::<4>(graph(), 0);
+        for x in 0..v {
+            assert_eq!(binary.has_path_to(&x), dary.has_path_to(&x));
+            assert_eq!(binary.get_distance_to(&x), dary.get_distance_to(&x));
+        }
+    }
 }
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::fundamentals::uf::RollbackUF;
+
+/// One step of a timeline given to `DynamicConnectivity::solve`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Adds an undirected edge between two vertices. The same edge may be added more than once.
+    Add(usize, usize),
+    /// Removes one copy of an edge that was added earlier.
+    Remove(usize, usize),
+    /// Asks whether two vertices are connected at this point of the timeline.
+    Connected(usize, usize),
+}
+
+/// Offline dynamic connectivity answers "are v and w connected at time t?" over a graph whose edges
+/// are added and removed, when the whole timeline of events is known up front.
+///
+/// Every edge is alive during an interval of the timeline. The intervals are stored in a segment tree
+/// over time, so each one is split over *O(log T)* nodes. A depth-first walk of the tree unions the edges
+/// of a node on the way down and rolls them back with a `RollbackUF` on the way up, so when the walk
+/// reaches the leaf of a query, exactly the edges alive at that time have been unioned.
+///
+/// With *T* events on *V* vertices, `solve` runs in *O(T log T log V)* time.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::graph::dynamic_connectivity::{DynamicConnectivity, Event};
+///
+/// let mut timeline = DynamicConnectivity::new(4);
+/// timeline.add_edge(0, 1);
+/// timeline.add_edge(1, 2);
+/// timeline.connected(0, 2);
+/// timeline.remove_edge(0, 1);
+/// timeline.connected(0, 2);
+/// timeline.connected(1, 2);
+///
+/// assert_eq!(timeline.solve(), vec![true, false, true]);
+///
+/// let events = [Event::Add(2, 3), Event::Connected(3, 2), Event::Remove(3, 2), Event::Connected(2, 3)];
+/// assert_eq!(DynamicConnectivity::from_events(4, &events).solve(), vec![true, false]);
+/// ```
+pub struct DynamicConnectivity {
+    n: usize,
+    events: Vec<Event>,
+}
+
+impl DynamicConnectivity {
+    /// Create an empty timeline over `n` vertices.
+    pub fn new(n: usize) -> Self {
+        DynamicConnectivity { n, events: Vec::new() }
+    }
+
+    /// Create a timeline over `n` vertices from a list of events.
+    pub fn from_events(n: usize, events: &[Event]) -> Self {
+        let mut timeline = DynamicConnectivity::new(n);
+        for e in events {
+            timeline.push(*e);
+        }
+        timeline
+    }
+
+    /// Appends an event to the timeline. Panics if a vertex is out of bounds.
+    pub fn push(&mut self, event: Event) {
+        let (Event::Add(v, w) | Event::Remove(v, w) | Event::Connected(v, w)) = event;
+        if v >= self.n || w >= self.n {
+            panic!("Out of bounds!!")
+        }
+        self.events.push(event);
+    }
+
+    /// Appends the addition of an edge between v and w.
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.push(Event::Add(v, w))
+    }
+
+    /// Appends the removal of an edge between v and w.
+    pub fn remove_edge(&mut self, v: usize, w: usize) {
+        self.push(Event::Remove(v, w))
+    }
+
+    /// Appends a query whether v and w are connected.
+    pub fn connected(&mut self, v: usize, w: usize) {
+        self.push(Event::Connected(v, w))
+    }
+
+    /// Returns the number of events in the timeline.
+    pub fn size(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Answers every query, in the order they appear in the timeline.
+    /// Panics if an edge is removed that is not in the graph at that time.
+    pub fn solve(&self) -> Vec<bool> {
+        let t = self.events.len();
+        if t == 0 {
+            return Vec::new();
+        }
+
+        // The times at which each copy of an edge was added and not yet removed
+        let mut alive: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut tree: Vec<Vec<(usize, usize)>> = vec![Vec::new(); 4 * t];
+        for (time, event) in self.events.iter().enumerate() {
+            match *event {
+                Event::Add(v, w) => alive.entry(edge(v, w)).or_default().push(time),
+                Event::Remove(v, w) => {
+                    let start = alive.get_mut(&edge(v, w)).and_then(|starts| starts.pop())
+                        .unwrap_or_else(|| panic!("Edge {}-{} is removed at time {} but is not in the graph!", v, w, time));
+                    insert(&mut tree, 1, 0, t, start, time, edge(v, w));
+                }
+                Event::Connected(..) => (),
+            }
+        }
+        for (e, starts) in alive {
+            for start in starts {
+                insert(&mut tree, 1, 0, t, start, t, e);
+            }
+        }
+
+        let mut uf = RollbackUF::new(self.n);
+        let mut answers = Vec::new();
+        self.walk(&tree, 1, 0, t, &mut uf, &mut answers);
+        answers
+    }
+
+    /// Unions the edges of `node`, which covers the times `lo..hi`, and visits its children
+    fn walk(&self, tree: &[Vec<(usize, usize)>], node: usize, lo: usize, hi: usize, uf: &mut RollbackUF, answers: &mut Vec<bool>) {
+        let snapshot = uf.snapshot();
+        for &(v, w) in &tree[node] {
+            uf.union(v, w);
+        }
+        if hi - lo == 1 {
+            if let Event::Connected(v, w) = self.events[lo] {
+                answers.push(uf.connected(v, w));
+            }
+        } else {
+            let mid = lo + (hi - lo) / 2;
+            self.walk(tree, 2 * node, lo, mid, uf, answers);
+            self.walk(tree, 2 * node + 1, mid, hi, uf, answers);
+        }
+        uf.rollback_to(snapshot);
+    }
+}
+
+/// The same key for both directions of an undirected edge
+fn edge(v: usize, w: usize) -> (usize, usize) {
+    if v < w { (v, w) } else { (w, v) }
+}
+
+/// Stores `e` in the nodes that together cover the times `from..to`, where `node` covers `lo..hi`
+fn insert(tree: &mut [Vec<(usize, usize)>], node: usize, lo: usize, hi: usize, from: usize, to: usize, e: (usize, usize)) {
+    if to <= lo || hi <= from {
+        return;
+    }
+    if from <= lo && hi <= to {
+        tree[node].push(e);
+        return;
+    }
+    let mid = lo + (hi - lo) / 2;
+    insert(tree, 2 * node, lo, mid, from, to, e);
+    insert(tree, 2 * node + 1, mid, hi, from, to, e);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fundamentals::uf::WeightedQuickUnionUF, randomization::lcg_random::Lcg};
+
+    use super::{DynamicConnectivity, Event};
+
+    /// Answers the queries by rebuilding a union find from the live edges at every query
+    fn brute_force(n: usize, events: &[Event]) -> Vec<bool> {
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut answers = Vec::new();
+        for event in events {
+            match *event {
+                Event::Add(v, w) => edges.push((v, w)),
+                Event::Remove(v, w) => {
+                    let i = edges.iter().position(|e| *e == (v, w) || *e == (w, v)).unwrap();
+                    edges.swap_remove(i);
+                }
+                Event::Connected(v, w) => {
+                    let mut uf = WeightedQuickUnionUF::new(n);
+                    for &(p, q) in &edges {
+                        uf.union(p, q);
+                    }
+                    answers.push(uf.connected(v, w));
+                }
+            }
+        }
+        answers
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = Lcg::new(17);
+        let n = 12;
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut events = Vec::new();
+        for _ in 0..600 {
+            let choice = rng.uniform(3);
+            if choice == 0 || edges.is_empty() {
+                let e = (rng.uniform(n), rng.uniform(n));
+                edges.push(e);
+                events.push(Event::Add(e.0, e.1));
+            } else if choice == 1 {
+                let (v, w) = edges.swap_remove(rng.uniform(edges.len()));
+                // Remove in either direction
+                events.push(if rng.uniform(2) == 0 { Event::Remove(v, w) } else { Event::Remove(w, v) });
+            } else {
+                events.push(Event::Connected(rng.uniform(n), rng.uniform(n)));
+            }
+        }
+        let timeline = DynamicConnectivity::from_events(n, &events);
+        assert_eq!(timeline.size(), 600);
+        assert_eq!(timeline.solve(), brute_force(n, &events));
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        let mut timeline = DynamicConnectivity::new(2);
+        timeline.add_edge(0, 1);
+        timeline.add_edge(1, 0);
+        timeline.remove_edge(0, 1);
+        timeline.connected(0, 1);
+        timeline.remove_edge(0, 1);
+        timeline.connected(0, 1);
+        assert_eq!(timeline.solve(), vec![true, false]);
+        assert!(DynamicConnectivity::new(3).solve().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_missing_edge() {
+        let mut timeline = DynamicConnectivity::new(3);
+        timeline.add_edge(0, 1);
+        timeline.remove_edge(1, 2);
+        timeline.solve();
+    }
+}
@@ -1,6 +1,6 @@
-use std::{collections::linked_list::Iter, rc::Rc};
+use std::{collections::linked_list::Iter, io::{self, Write}, rc::Rc};
 
-use crate::fundamentals::bag::Bag;
+use crate::{fundamentals::bag::Bag, io::input::{In, InputError}};
 
 use super::edge::Edge;
 
@@ -59,10 +59,116 @@ impl EdgeWeightedGraph {
         list
     }
 
+    /// Reads a graph in the algs4 format: the number of vertices, the number of edges, and then
+    /// one line `v w weight` per edge, e.g. `tinyEWG.txt`.
+    /// Edge weights are integers, so a weight like `0.35` is read in fixed point with `decimals`
+    /// digits after the point: with 2 decimals it becomes 35. A weight with more digits is an error.
+    /// `decimals` can be at most 38, the most a `u128` weight can hold, any weight is an error beyond that.
+    pub fn from_reader(input: &mut In, decimals: u32) -> Result<Self, InputError> {
+        let mut g = EdgeWeightedGraph::new(input.read()?);
+        let e: usize = input.read()?;
+        for _ in 0..e {
+            let v = input.read_index(g.V)?;
+            let w = input.read_index(g.V)?;
+            let weight = input.read_decimal(decimals)?;
+            g.add_edge(Edge::new(v, w, weight));
+        }
+        Ok(g)
+    }
+
+    /// Writes the graph in the format read by `from_reader`, with weights in fixed point with `decimals` digits.
+    /// More than 38 decimals is an error of kind `InvalidInput`, and nothing is written.
+    pub fn write_to(&self, out: &mut impl Write, decimals: u32) -> io::Result<()> {
+        let scale = 10u128.checked_pow(decimals).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("{} decimals do not fit in a u128 weight", decimals))
+        })?;
+        writeln!(out, "{}", self.V)?;
+        writeln!(out, "{}", self.E)?;
+        for e in self.edges().iterator() {
+            let v = *e.either();
+            write!(out, "{} {} ", v, e.other(v))?;
+            if decimals == 0 {
+                writeln!(out, "{}", e.weight)?;
+            } else {
+                writeln!(out, "{}.{:0width$}", e.weight / scale, e.weight % scale, width = decimals as usize)?;
+            }
+        }
+        Ok(())
+    }
 
     fn validate(&self, v:&usize){
         if v>= &self.V {
             panic!("Out of bounds!!")
         }
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::io::input::{In, InputErrorKind};
+
+    use super::EdgeWeightedGraph;
+
+    const TINY_EWG: &str = "8\n16\n4 5 0.35\n4 7 0.37\n5 7 0.28\n0 7 0.16\n1 5 0.32\n0 4 0.38\n2 3 0.17\n1 7 0.19\n\
+        0 2 0.26\n1 2 0.36\n1 3 0.29\n2 7 0.34\n6 2 0.40\n3 6 0.52\n6 0 0.58\n6 4 0.93\n";
+
+    fn sorted_edges(g: &EdgeWeightedGraph) -> Vec<(usize, usize, u128)> {
+        let mut edges: Vec<(usize, usize, u128)> = g.edges().iterator().map(|e| {
+            let v = *e.either();
+            let w = *e.other(v);
+            (v.min(w), v.max(w), e.weight)
+        }).collect();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn test_read_tiny_ewg() {
+        let mut g = EdgeWeightedGraph::from_reader(&mut In::from_text(TINY_EWG), 2).unwrap();
+        assert_eq!((g.V, g.E), (8, 16));
+        assert_eq!(g.degree(&6), 4);
+        assert_eq!(g.edges().iterator().map(|e| e.weight).sum::<u128>(), 590);
+        assert!(sorted_edges(&g).contains(&(0, 6, 58)));
+
+        let mut out = Vec::new();
+        g.write_to(&mut out, 2).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("8\n16\n"));
+        assert!(text.contains(" 0.40\n"));
+        let again = EdgeWeightedGraph::from_reader(&mut In::from_text(&text), 2).unwrap();
+        assert_eq!(sorted_edges(&again), sorted_edges(&g));
+    }
+
+    #[test]
+    fn test_integer_weights_and_errors() {
+        let g = EdgeWeightedGraph::from_reader(&mut In::from_text("2\n1\n0 1 12\n"), 0).unwrap();
+        let mut out = Vec::new();
+        g.write_to(&mut out, 0).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "2\n1\n0 1 12\n");
+
+        let err = EdgeWeightedGraph::from_reader(&mut In::from_text(TINY_EWG), 1).err().unwrap();
+        assert_eq!((err.line, err.column), (3, 5));
+        assert!(matches!(err.kind, InputErrorKind::InvalidToken { .. }));
+        let err = EdgeWeightedGraph::from_reader(&mut In::from_text("2\n1\n0 2 1\n"), 0).err().unwrap();
+        assert_eq!(err.kind, InputErrorKind::OutOfRange { value: 2, bound: 2 });
+        assert!(EdgeWeightedGraph::from_reader(&mut In::from_text("2\n1\n0 1 1\n"), 39).is_err());
+        let err = EdgeWeightedGraph::from_reader(&mut In::from_text(&format!("2\n1\n0 1 {}\n", "9".repeat(45))), 0)
+            .err().unwrap();
+        assert_eq!((err.line, err.column), (3, 5));
+        assert!(matches!(err.kind, InputErrorKind::InvalidToken { .. }));
+    }
+
+    #[test]
+    fn test_write_too_many_decimals() {
+        let g = EdgeWeightedGraph::from_reader(&mut In::from_text("2\n1\n0 1 1\n"), 38).unwrap();
+        let mut out = Vec::new();
+        g.write_to(&mut out, 38).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("2\n1\n0 1 1.{}\n", "0".repeat(38)));
+
+        let mut out = Vec::new();
+        let err = g.write_to(&mut out, 39).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+    }
+}
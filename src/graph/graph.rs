@@ -1,4 +1,6 @@
-use crate::fundamentals::bag::Bag;
+use std::io::{self, Write};
+
+use crate::{fundamentals::bag::Bag, io::input::{In, InputError}};
 
 
 pub struct Graph{
@@ -54,6 +56,39 @@ impl Graph {
         self.E
     }
 
+    /// Reads a graph in the algs4 format: the number of vertices, the number of edges,
+    /// and then one pair of vertices per edge, e.g. `tinyG.txt`.
+    /// A vertex outside the graph is an error rather than a panic.
+    pub fn from_reader(input: &mut In) -> Result<Graph, InputError> {
+        let mut g = Graph::new(input.read()?);
+        let e: usize = input.read()?;
+        for _ in 0..e {
+            let v = input.read_index(g.V)?;
+            let w = input.read_index(g.V)?;
+            g.add_edge(v, w);
+        }
+        Ok(g)
+    }
+
+    /// Writes the graph in the format read by `from_reader`
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{}", self.V)?;
+        writeln!(out, "{}", self.E)?;
+        for v in 0..self.V {
+            // A self-loop is in the adjacency list of v twice
+            let mut self_loops = 0;
+            for w in self.adj_vertices(&v) {
+                if *w > v || (*w == v && self_loops % 2 == 0) {
+                    writeln!(out, "{} {}", v, w)?;
+                }
+                if *w == v {
+                    self_loops += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Creates a deep copy of the graph
     pub fn clone(&self) -> Graph {
         let mut temp: Vec<Bag<usize>> = Vec::new();
@@ -67,8 +102,27 @@ impl Graph {
 
 #[cfg(test)]
 mod tests {
+    use crate::io::input::{In, InputErrorKind};
+
     use super::Graph;
 
+    const TINY_G: &str = "13\n13\n0 5\n4 3\n0 1\n9 12\n6 4\n5 4\n0 2\n11 12\n9 10\n0 6\n7 8\n9 11\n5 3\n";
+
+    fn sorted_edges(g: &Graph) -> Vec<(usize, usize)> {
+        let mut out = Vec::new();
+        g.write_to(&mut out).unwrap();
+        let mut input = In::from_text(std::str::from_utf8(&out).unwrap());
+        input.read_int().unwrap();
+        input.read_int().unwrap();
+        let mut edges = Vec::new();
+        while !input.is_empty() {
+            let (v, w) = (input.read::<usize>().unwrap(), input.read::<usize>().unwrap());
+            edges.push((v.min(w), v.max(w)));
+        }
+        edges.sort();
+        edges
+    }
+
     #[test]
     fn test_create_graph(){
         let mut g = Graph::new(4);
@@ -85,4 +139,37 @@ mod tests {
         let mut it = g.adj_vertices(&0);
         assert_eq!(it.next(), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_read_tiny_g(){
+        let mut g = Graph::from_reader(&mut In::from_text(TINY_G)).unwrap();
+        assert_eq!(g.get_v(), 13);
+        assert_eq!(g.get_e(), 13);
+        assert_eq!(g.degree(0), 4);
+        assert_eq!(g.degree(7), 1);
+        assert_eq!(sorted_edges(&g).len(), 13);
+    }
+
+    #[test]
+    fn test_write_round_trip(){
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1);
+        g.add_edge(2, 2);
+        g.add_edge(1, 0);
+        let mut out = Vec::new();
+        g.write_to(&mut out).unwrap();
+        let again = Graph::from_reader(&mut In::from_text(std::str::from_utf8(&out).unwrap())).unwrap();
+        assert_eq!(again.get_e(), 3);
+        assert_eq!(sorted_edges(&again), vec![(0, 1), (0, 1), (2, 2)]);
+        assert_eq!(sorted_edges(&again), sorted_edges(&g));
+    }
+
+    #[test]
+    fn test_read_errors(){
+        let err = Graph::from_reader(&mut In::from_text("3\n2\n0 1\n1 3\n")).err().unwrap();
+        assert_eq!(err.kind, InputErrorKind::OutOfRange { value: 3, bound: 3 });
+        assert_eq!((err.line, err.column), (4, 3));
+        let err = Graph::from_reader(&mut In::from_text("3\n2\n0 1\n")).err().unwrap();
+        assert_eq!(err.kind, InputErrorKind::UnexpectedEnd);
+    }
+}
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 
-use crate::fundamentals::{queue::Queue, uf::WeightedQuickUnionUF};
+use crate::fundamentals::{queue::Queue, uf::WeightedQuickUnionPathCompressionUF};
 
 use super::{edge::Edge, edge_weighted_graph::EdgeWeightedGraph};
 
@@ -54,7 +54,7 @@ impl KruskalMST {
 
         edges.sort_by(|a, b| a.cmp(b));
 
-        let mut uf = WeightedQuickUnionUF::new(G.V);
+        let mut uf = WeightedQuickUnionPathCompressionUF::new(G.V);
 
         let mut i: usize = 0;
         loop {
@@ -6,4 +6,4 @@ pub mod edge;
 pub mod kruskal_mst;
 pub mod edge_weighted_graph;
 pub mod dijkstra_sp;
-
+pub mod dynamic_connectivity;
@@ -0,0 +1,322 @@
+use std::{
+    error::Error,
+    fmt,
+    fs,
+    io::{self, Read},
+    path::Path,
+    str::FromStr,
+};
+
+/// The io input module reads whitespace-separated tokens and lines from a file, standard input
+/// or a string, like `In` from Algorithms, 4th Edition by Robert Sedgewick and Kevin Wayne.
+/// It is meant for the book's data files, such as `tinyG.txt` or `tinyUF.txt`.
+///
+/// The whole input is read when the reader is created. After that no method panics: reading past
+/// the end, or a token that is not a number, returns an `InputError` with the line and column
+/// (both counted from 1) of the token, or of the end of the input.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::io::input::{In, InputErrorKind};
+///
+/// let mut input = In::from_text("3\n1 2.5 three\nthe rest of a line\n7 x");
+/// assert_eq!(input.read_int(), Ok(3));
+/// assert_eq!(input.read_int(), Ok(1));
+/// assert_eq!(input.read_double(), Ok(2.5));
+/// assert_eq!(input.read_string(), Ok("three".to_string()));
+/// // read_line reads the rest of the current line, which is empty after "three"
+/// assert_eq!(input.read_line(), Ok("".to_string()));
+/// assert_eq!(input.read_line(), Ok("the rest of a line".to_string()));
+///
+/// let err = input.read_all_ints().unwrap_err();
+/// assert_eq!((err.line, err.column), (4, 3));
+/// assert!(matches!(err.kind, InputErrorKind::InvalidToken { .. }));
+/// ```
+pub struct In {
+    text: String,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+/// What went wrong while reading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputErrorKind {
+    /// The input ended before a token or line could be read
+    UnexpectedEnd,
+    /// A token that does not parse as the expected type
+    InvalidToken { token: String, expected: &'static str },
+    /// An index that is not below its bound, such as a vertex that is not in the graph
+    OutOfRange { value: usize, bound: usize },
+}
+
+/// An error while reading, with the line and column where it happened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub kind: InputErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            InputErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            InputErrorKind::InvalidToken { token, expected } => write!(f, "expected {} but found '{}'", expected, token)?,
+            InputErrorKind::OutOfRange { value, bound } => write!(f, "{} is out of range, it must be below {}", value, bound)?,
+        }
+        write!(f, " at line {}, column {}", self.line, self.column)
+    }
+}
+
+impl Error for InputError {}
+
+impl In {
+    /// Create a reader over a string.
+    pub fn from_text(text: &str) -> In {
+        In { text: text.to_string(), pos: 0, line: 1, column: 1 }
+    }
+
+    /// Create a reader over everything `reader` produces.
+    pub fn from_reader(mut reader: impl Read) -> io::Result<In> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(In { text, pos: 0, line: 1, column: 1 })
+    }
+
+    /// Create a reader over a file.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<In> {
+        In::from_reader(fs::File::open(path)?)
+    }
+
+    /// Create a reader over standard input.
+    pub fn stdin() -> io::Result<In> {
+        In::from_reader(io::stdin().lock())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn advance(&mut self, c: char) {
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.advance(c);
+        }
+    }
+
+    fn error(&self, kind: InputErrorKind, line: usize, column: usize) -> InputError {
+        InputError { kind, line, column }
+    }
+
+    /// Checks if only whitespace is left.
+    pub fn is_empty(&self) -> bool {
+        self.text[self.pos..].trim_start().is_empty()
+    }
+
+    /// Checks if there is anything left, even an empty line.
+    pub fn has_next_line(&self) -> bool {
+        self.pos < self.text.len()
+    }
+
+    /// Reads the next token and parses it, the error names the expected type.
+    fn read_token<T: FromStr>(&mut self, expected: &'static str) -> Result<T, InputError> {
+        self.skip_whitespace();
+        let (line, column) = (self.line, self.column);
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            self.advance(c);
+        }
+        let token = &self.text[start..self.pos];
+        if token.is_empty() {
+            return Err(self.error(InputErrorKind::UnexpectedEnd, line, column));
+        }
+        token.parse().map_err(|_| self.error(InputErrorKind::InvalidToken { token: token.to_string(), expected }, line, column))
+    }
+
+    /// Reads the next token as any type that implements `FromStr`.
+    pub fn read<T: FromStr>(&mut self) -> Result<T, InputError> {
+        self.read_token(std::any::type_name::<T>())
+    }
+
+    /// Reads the next token as an integer.
+    pub fn read_int(&mut self) -> Result<i64, InputError> {
+        self.read_token("an integer")
+    }
+
+    /// Reads the next token as a floating point number.
+    pub fn read_double(&mut self) -> Result<f64, InputError> {
+        self.read_token("a number")
+    }
+
+    /// Reads the next token as an index below `bound`, such as a vertex of a graph with `bound` vertices.
+    pub fn read_index(&mut self, bound: usize) -> Result<usize, InputError> {
+        self.skip_whitespace();
+        let (line, column) = (self.line, self.column);
+        let value = self.read_token("a non-negative integer")?;
+        if value >= bound {
+            return Err(self.error(InputErrorKind::OutOfRange { value, bound }, line, column));
+        }
+        Ok(value)
+    }
+
+    /// Reads the next token as a non-negative decimal number in fixed point with `decimals` digits
+    /// after the point, so `0.35` with 2 decimals is 35. The conversion is exact, a token with more
+    /// digits after the point than `decimals` is an error, and so is any token when `decimals` is over 38,
+    /// since 10^39 does not fit in a `u128`.
+    pub fn read_decimal(&mut self, decimals: u32) -> Result<u128, InputError> {
+        self.skip_whitespace();
+        let (line, column) = (self.line, self.column);
+        let token: String = self.read_token("a decimal number")?;
+        let (whole, fraction) = token.split_once('.').unwrap_or((&token, ""));
+        let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        let scale = 10u128.checked_pow(decimals);
+        let value = match scale {
+            Some(scale) if digits(whole) && digits(fraction) && !(whole.is_empty() && fraction.is_empty())
+                && fraction.len() <= decimals as usize => {
+                let whole: Option<u128> = if whole.is_empty() { Some(0) } else { whole.parse().ok() };
+                let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+                let fraction: Option<u128> = if padded.is_empty() { Some(0) } else { padded.parse().ok() };
+                whole.zip(fraction).and_then(|(w, f)| w.checked_mul(scale)?.checked_add(f))
+            }
+            _ => None,
+        };
+        value.ok_or_else(|| self.error(InputErrorKind::InvalidToken { token, expected: "a decimal number" }, line, column))
+    }
+
+    /// Reads the next whitespace-separated token.
+    pub fn read_string(&mut self) -> Result<String, InputError> {
+        self.read_token("a string")
+    }
+
+    /// Reads the rest of the current line, without the line break.
+    pub fn read_line(&mut self) -> Result<String, InputError> {
+        if !self.has_next_line() {
+            return Err(self.error(InputErrorKind::UnexpectedEnd, self.line, self.column));
+        }
+        let start = self.pos;
+        let mut end = self.text.len();
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                end = self.pos;
+                self.advance(c);
+                break;
+            }
+            self.advance(c);
+        }
+        Ok(self.text[start..end].trim_end_matches('\r').to_string())
+    }
+
+    /// Reads every remaining token as an integer.
+    pub fn read_all_ints(&mut self) -> Result<Vec<i64>, InputError> {
+        let mut ints = Vec::new();
+        while !self.is_empty() {
+            ints.push(self.read_int()?);
+        }
+        Ok(ints)
+    }
+
+    /// Reads every remaining whitespace-separated token.
+    pub fn read_all_strings(&mut self) -> Vec<String> {
+        let strings = self.text[self.pos..].split_whitespace().map(|s| s.to_string()).collect();
+        while let Some(c) = self.peek() {
+            self.advance(c);
+        }
+        strings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{In, InputError, InputErrorKind};
+
+    #[test]
+    fn test_tiny_uf() {
+        let mut input = In::from_reader(Cursor::new("10\n4 3\n3 8\n6 5\n")).unwrap();
+        assert_eq!(input.read::<usize>(), Ok(10));
+        let mut pairs = Vec::new();
+        while !input.is_empty() {
+            pairs.push((input.read_int().unwrap(), input.read_int().unwrap()));
+        }
+        assert_eq!(pairs, vec![(4, 3), (3, 8), (6, 5)]);
+        assert_eq!(input.read_int(), Err(InputError { kind: InputErrorKind::UnexpectedEnd, line: 5, column: 1 }));
+    }
+
+    #[test]
+    fn test_positions_count_characters() {
+        let mut input = In::from_text("  é 12\r\n\t-3 4x");
+        assert_eq!(input.read_string(), Ok("é".to_string()));
+        assert_eq!(input.read_int(), Ok(12));
+        assert_eq!(input.read_int(), Ok(-3));
+        let err = input.read_int().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.to_string(), "expected an integer but found '4x' at line 2, column 5");
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut input = In::from_text("first line\r\n\nlast");
+        assert_eq!(input.read_line(), Ok("first line".to_string()));
+        assert_eq!(input.read_line(), Ok("".to_string()));
+        assert!(input.has_next_line());
+        assert_eq!(input.read_line(), Ok("last".to_string()));
+        assert!(!input.has_next_line());
+        assert_eq!(input.read_line().unwrap_err().kind, InputErrorKind::UnexpectedEnd);
+    }
+
+    #[test]
+    fn test_read_all() {
+        let mut input = In::from_text("it was the best\nof times ");
+        assert_eq!(input.read_string(), Ok("it".to_string()));
+        assert_eq!(input.read_all_strings(), vec!["was", "the", "best", "of", "times"]);
+        assert!(input.is_empty());
+        assert_eq!(In::from_text(" 1 -2\n3 ").read_all_ints(), Ok(vec![1, -2, 3]));
+        assert_eq!(In::from_text("").read_all_ints(), Ok(vec![]));
+        assert_eq!(In::from_text("1.5").read_double(), Ok(1.5));
+        assert!(In::from_file("/this/file/does/not/exist").is_err());
+    }
+
+    #[test]
+    fn test_indices_and_decimals() {
+        let mut input = In::from_text("3 10
+0.35 .5 7 1.234 -1");
+        assert_eq!(input.read_index(10), Ok(3));
+        let err = input.read_index(10).unwrap_err();
+        assert_eq!(err, InputError { kind: InputErrorKind::OutOfRange { value: 10, bound: 10 }, line: 1, column: 3 });
+        assert_eq!(input.read_decimal(2), Ok(35));
+        assert_eq!(input.read_decimal(2), Ok(50));
+        assert_eq!(input.read_decimal(0), Ok(7));
+        assert_eq!(input.read_decimal(2).unwrap_err().column, 11);
+        assert!(input.read_decimal(2).is_err());
+        assert_eq!(In::from_text(".").read_decimal(1).unwrap_err().kind,
+            InputErrorKind::InvalidToken { token: ".".to_string(), expected: "a decimal number" });
+    }
+
+    #[test]
+    fn test_decimal_too_large_for_u128() {
+        let token = "9".repeat(45);
+        assert_eq!(In::from_text(&token).read_decimal(0).unwrap_err().kind,
+            InputErrorKind::InvalidToken { token: token.clone(), expected: "a decimal number" });
+        assert!(In::from_text(&format!("{}.5", token)).read_decimal(1).is_err());
+        assert_eq!(In::from_text(&u128::MAX.to_string()).read_decimal(0), Ok(u128::MAX));
+        assert!(In::from_text("340282366920938463463374607431768211456").read_decimal(0).is_err());
+    }
+}
@@ -3,6 +3,7 @@ pub mod searching;
 pub mod sorting;
 pub mod randomization;
 pub mod graph;
+pub mod io;
 
 #[cfg(test)]
 mod tests {
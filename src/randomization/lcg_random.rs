@@ -41,6 +41,72 @@ pub fn lcg_generate(size: usize) -> Vec<u32> {
     random_list
 }
 
+/// A seedable linear congruence generator with the same GLIBC parameters as `lcg_generate`.
+/// Two generators created with the same seed produce the same sequence, which makes randomized
+/// algorithms and their tests repeatable.
+/// 
+/// The low bits of this generator have short periods, so `uniform` and `next_f64` draw on the high bits.
+/// 
+/// # Examples
+/// ```
+/// use itualgs_rs::randomization::lcg_random::Lcg;
+/// 
+/// let mut a = Lcg::new(42);
+/// let mut b = Lcg::new(42);
+/// assert_eq!(a.next_u32(), b.next_u32());
+/// 
+/// let roll = a.uniform(6);
+/// assert!(roll < 6);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Lcg {
+    x: u64,
+}
+
+impl Lcg {
+    /// Creates a generator with the given seed
+    pub fn new(seed: u64) -> Lcg {
+        Lcg { x: seed % 2147483648 }
+    }
+
+    /// Creates a generator seeded with the current system time
+    pub fn from_time() -> Lcg {
+        Lcg::new(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .subsec_nanos() as u64)
+    }
+
+    /// Returns the next number of the sequence, in `0..2^31`
+    pub fn next_u32(&mut self) -> u32 {
+        self.x = (1103515245 * self.x + 12345) % 2147483648;
+        self.x as u32
+    }
+
+    /// Returns a number in `0..n` drawn uniformly at random, `n` must be positive
+    pub fn uniform(&mut self, n: usize) -> usize {
+        if n == 0 {
+            panic!("uniform needs a positive bound!")
+        }
+        // Scale 62 random bits to the range, so the result depends on the high bits
+        let bits = (self.next_u32() as u128) << 31 | self.next_u32() as u128;
+        ((bits * n as u128) >> 62) as usize
+    }
+
+    /// Returns a number in `[0, 1)` drawn uniformly at random
+    pub fn next_f64(&mut self) -> f64 {
+        self.next_u32() as f64 / 2147483648.0
+    }
+
+    /// Shuffles the slice uniformly at random with the Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, a: &mut [T]) {
+        for i in (1..a.len()).rev() {
+            let j = self.uniform(i + 1);
+            a.swap(i, j);
+        }
+    }
+}
+
 pub fn shuffle_list<T>(a: &mut Vec<T>){
     let rand = lcg_generate(a.len()*2);
     let len = a.len();
@@ -57,7 +123,7 @@ pub fn shuffle_list<T>(a: &mut Vec<T>){
 mod tests {
     use crate::randomization::lcg_random::shuffle_list;
 
-    use super::lcg_generate;
+    use super::{lcg_generate, Lcg};
 
     #[test]
     fn test_size() {
@@ -98,6 +164,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_seeded_is_repeatable() {
+        let mut a = Lcg::new(7);
+        let mut b = Lcg::new(7);
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+        let mut list: Vec<u32> = (0..50).collect();
+        let mut other = list.clone();
+        a.shuffle(&mut list);
+        b.shuffle(&mut other);
+        assert_eq!(list, other);
+        list.sort();
+        assert_eq!(list, (0..50).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_uniform_small_bounds() {
+        // The lowest bit of the raw sequence alternates, uniform must not inherit that
+        let mut rng = Lcg::new(1);
+        let mut counts = [0; 2];
+        let mut last = rng.uniform(2);
+        let mut changes = 0;
+        for _ in 0..10_000 {
+            let x = rng.uniform(2);
+            counts[x] += 1;
+            if x != last {
+                changes += 1;
+            }
+            last = x;
+        }
+        assert!(counts[0] > 4_500 && counts[1] > 4_500);
+        assert!(changes < 6_000);
+    }
+
     #[test]
     fn test_shuffle() {
         let mut list = vec![2,3,1,5,234,324,1234,123,4,1234,645];
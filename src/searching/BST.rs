@@ -1,8 +1,16 @@
 use std::{cmp::Ordering::{*, self}, mem};
+
+use crate::fundamentals::queue::Queue;
+
+// Comparator and Natural live in fundamentals::comparator, they are re-exported here for existing imports
+pub use crate::fundamentals::comparator::{Comparator, Natural};
 /// The BST class represents an ordered symbol table og generic key pair values
-/// It supports the operations `put`, `get`, `delete_max` and `delete_min`.
+/// It supports the operations `put`, `get`, `delete`, `delete_max`, `delete_min`, `min`, `max`, `floor`, `ceiling`, `keys` and `range`.
 /// 
 /// Every method should take constant worst-case running time: *O(N)*. With an average of *O(log N)*
+/// 
+/// Keys are ordered by their `Ord` implementation unless the table is created with `with_comparator`,
+/// which takes any `Comparator`, such as a closure `Fn(&T, &T) -> Ordering`.
 /// Author: AlbertRossJoh
 /// 
 /// # Examples
@@ -25,6 +33,11 @@ use std::{cmp::Ordering::{*, self}, mem};
 /// 
 /// assert_eq!(another_bst.get(&2).unwrap(), &"val2");
 /// 
+/// let mut reversed = BST::with_comparator(|a: &u8, b: &u8| b.cmp(a));
+/// reversed.put(1, "one");
+/// reversed.put(2, "two");
+/// 
+/// assert_eq!(reversed.min().unwrap(), &2);
 /// ```
 
 struct Value<T,K> {
@@ -41,20 +54,16 @@ struct Node<T,K> {
 }
 
 
-impl<T, K> Node<T,K> 
-    where T:Ord
-    {
+impl<T, K> Node<T,K> {
     fn new(key: T, val: K) -> Self {
         Node { value: Value{ key: key, val: val }, left: None , right: None }
     }
-    fn cmp(&self, other: &Node<T,K>) -> Ordering{
-        other.value.key.cmp(&self.value.key)
-    }
 
-    fn cmp_to_key(&self, key: &T) -> Ordering {
-        key.cmp(&self.value.key)
+    fn cmp_to_key<C: Comparator<T>>(&self, key: &T, cmp: &C) -> Ordering {
+        cmp.compare(key, &self.value.key)
     }
 }
+
 fn get_min_node_mut<T,K>(node:&mut Edge<T,K>) -> &mut Edge<T,K>{
     let mut curr: *mut Edge<T,K> = node;
     unsafe{
@@ -81,8 +90,10 @@ fn get_max_node_mut<T,K>(node:&mut Edge<T,K>) -> &mut Edge<T,K>{
     }
 }
 
-pub struct BST<T,K>{
+pub struct BST<T,K,C = Natural>{
     root: Edge<T,K>,
+    n: usize,
+    cmp: C,
 }
 
 impl<T,K> BST<T,K> 
@@ -90,7 +101,64 @@ impl<T,K> BST<T,K>
 {
     
     pub fn new() -> Self {
-        BST { root: None }
+        BST { root: None, n: 0, cmp: Natural }
+    }
+
+    /// Builds a perfectly balanced table in linear time from pairs sorted by strictly ascending key.
+    /// Panics if the keys are not strictly ascending.
+    pub fn from_sorted_vec(pairs: Vec<(T,K)>) -> Self {
+        Self::from_sorted_vec_with_comparator(pairs, Natural)
+    }
+}
+
+impl<T,K,C> BST<T,K,C> 
+    where C: Comparator<T>
+{
+    /// Creates an empty table that orders its keys with `cmp` instead of their `Ord` implementation
+    pub fn with_comparator(cmp: C) -> Self {
+        BST { root: None, n: 0, cmp }
+    }
+
+    /// Builds a perfectly balanced table in linear time from pairs sorted by strictly ascending key under `cmp`.
+    /// Panics if the keys are not strictly ascending.
+    pub fn from_sorted_vec_with_comparator(pairs: Vec<(T,K)>, cmp: C) -> Self {
+        if pairs.windows(2).any(|p| cmp.compare(&p[0].0, &p[1].0) != Less) {
+            panic!("Keys are not strictly ascending!")
+        }
+        let n = pairs.len();
+        let mut it = pairs.into_iter();
+        BST { root: Self::build(n, &mut it), n, cmp }
+    }
+
+    /// Returns the number of key-value pairs in the table
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Returns true if the table holds no keys
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the number of nodes on the longest path from the root to a leaf, 0 for an empty table
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(&Node<T,K>, usize)> = Vec::new();
+        if let Some(root) = &self.root {
+            stack.push((root, 1));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            height = height.max(depth);
+            for child in [&node.left, &node.right].into_iter().flatten() {
+                stack.push((child, depth + 1));
+            }
+        }
+        height
+    }
+
+    /// Returns true if the table holds a value for `key`
+    pub fn contains(&self, key: &T) -> bool {
+        self.get(key).is_some()
     }
 
     pub fn get_root(&self) -> Option<&K>{
@@ -101,48 +169,211 @@ impl<T,K> BST<T,K>
     }
 
     pub fn delete_max(&mut self){
-        Self::del(get_max_node_mut(&mut self.root))
+        if self.root.is_some() {
+            self.n -= 1;
+        }
+        Self::del(get_max_node_mut(&mut self.root));
     }
 
     pub fn delete_min(&mut self){
-        Self::del(get_min_node_mut(&mut self.root))
+        if self.root.is_some() {
+            self.n -= 1;
+        }
+        Self::del(get_min_node_mut(&mut self.root));
     }
 
     pub fn delete(&mut self, key: &T){
-        let curr = self.get_mut(key);
+        let curr = self.get_edge_mut(key);
+        if curr.is_none() {
+            return;
+        }
         Self::del(curr);
+        self.n -= 1;
+    }
+
+    fn build(n: usize, it: &mut impl Iterator<Item = (T,K)>) -> Edge<T,K> {
+        if n == 0 {
+            return None;
+        }
+        let left = Self::build(n / 2, it);
+        let (key, val) = it.next().unwrap();
+        let right = Self::build(n - n / 2 - 1, it);
+        Some(Box::new(Node { value: Value { key, val }, left, right }))
+    }
+
+    /// Returns the smallest key in the table
+    pub fn min(&self) -> Option<&T> {
+        let mut curr = self.root.as_ref()?;
+        while let Some(ref left) = curr.left {
+            curr = left;
+        }
+        Some(&curr.value.key)
+    }
+
+    /// Returns the largest key in the table
+    pub fn max(&self) -> Option<&T> {
+        let mut curr = self.root.as_ref()?;
+        while let Some(ref right) = curr.right {
+            curr = right;
+        }
+        Some(&curr.value.key)
+    }
+
+    /// Returns the largest key less than or equal to `key`
+    pub fn floor(&self, key: &T) -> Option<&T> {
+        let mut curr = &self.root;
+        let mut best = None;
+        while let Some(ref node) = curr {
+            match node.cmp_to_key(key, &self.cmp) {
+                Less => curr = &node.left,
+                Greater => {
+                    best = Some(&node.value.key);
+                    curr = &node.right;
+                },
+                Equal => return Some(&node.value.key),
+            }
+        }
+        best
+    }
+
+    /// Returns the smallest key greater than or equal to `key`
+    pub fn ceiling(&self, key: &T) -> Option<&T> {
+        let mut curr = &self.root;
+        let mut best = None;
+        while let Some(ref node) = curr {
+            match node.cmp_to_key(key, &self.cmp) {
+                Less => {
+                    best = Some(&node.value.key);
+                    curr = &node.left;
+                },
+                Greater => curr = &node.right,
+                Equal => return Some(&node.value.key),
+            }
+        }
+        best
+    }
+
+    /// Returns every key in ascending order
+    pub fn keys(&self) -> Queue<&T> {
+        let mut queue = Queue::new();
+        Self::keys_of(&self.root, &mut queue);
+        queue
+    }
+
+    fn keys_of<'a>(node: &'a Edge<T,K>, queue: &mut Queue<&'a T>){
+        if let Some(e) = node {
+            Self::keys_of(&e.left, queue);
+            queue.enqueue(&e.value.key);
+            Self::keys_of(&e.right, queue);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn pairs_of<'a>(node: &'a Edge<T,K>, pairs: &mut Vec<(&'a T, &'a K)>){
+        if let Some(e) = node {
+            Self::pairs_of(&e.left, pairs);
+            pairs.push((&e.value.key, &e.value.val));
+            Self::pairs_of(&e.right, pairs);
+        }
+    }
+
+    /// Returns a cursor positioned at the smallest key greater than or equal to `key`.
+    /// If every key is smaller, the cursor points past the end of the table.
+    pub fn cursor_at(&mut self, key: &T) -> Cursor<'_,T,K,C> {
+        let mut cursor = Cursor { path: Vec::new(), tree: self };
+        let mut curr: *mut Edge<T,K> = &mut cursor.tree.root;
+        unsafe {
+            while let Some(ref mut node) = *curr {
+                let ord = node.cmp_to_key(key, &cursor.tree.cmp);
+                cursor.path.push(&mut **node);
+                match ord {
+                    Less => curr = &mut node.left,
+                    Greater => curr = &mut node.right,
+                    Equal => return cursor,
+                }
+            }
+        }
+        // We fell off to the right of the last node, so the ceiling is its successor
+        if let Some(last) = cursor.path.last() {
+            if unsafe { (**last).cmp_to_key(key, &cursor.tree.cmp) } == Greater {
+                cursor.move_next();
+            }
+        }
+        cursor
+    }
+
+    /// Returns a cursor positioned at the smallest key
+    pub fn cursor_front(&mut self) -> Cursor<'_,T,K,C> {
+        let mut cursor = Cursor { path: Vec::new(), tree: self };
+        cursor.move_next();
+        cursor
+    }
+
+    /// Returns a cursor positioned at the largest key
+    pub fn cursor_back(&mut self) -> Cursor<'_,T,K,C> {
+        let mut cursor = Cursor { path: Vec::new(), tree: self };
+        cursor.move_prev();
+        cursor
+    }
+
+    /// Returns every key-value pair with `lo <= key <= hi` in ascending key order
+    pub fn range(&self, lo: &T, hi: &T) -> Queue<(&T, &K)> {
+        let mut queue = Queue::new();
+        self.range_of(&self.root, lo, hi, &mut queue);
+        queue
+    }
+
+    fn range_of<'a>(&self, node: &'a Edge<T,K>, lo: &T, hi: &T, queue: &mut Queue<(&'a T, &'a K)>){
+        if let Some(e) = node {
+            let cmp_lo = e.cmp_to_key(lo, &self.cmp);
+            let cmp_hi = e.cmp_to_key(hi, &self.cmp);
+            if cmp_lo == Less {
+                self.range_of(&e.left, lo, hi, queue);
+            }
+            if cmp_lo != Greater && cmp_hi != Less {
+                queue.enqueue((&e.value.key, &e.value.val));
+            }
+            if cmp_hi == Greater {
+                self.range_of(&e.right, lo, hi, queue);
+            }
+        }
     }
 
     /// Credit to https://codereview.stackexchange.com/users/32521/shepmaster
     /// https://codereview.stackexchange.com/questions/133209/binary-tree-implementation-in-rust/133776#133776
-    fn del(node: &mut Edge<T,K>){
+    /// Returns the removed key-value pair
+    fn del(node: &mut Edge<T,K>) -> Option<Value<T,K>>{
         if let Some(mut e) = node.take() {
             match (e.left.take(), e.right.take()) {
-                (None, None) => (),
+                (None, None) => return Some(e.value),
                 (Some(o), None) | 
-                (None, Some(o)) => *node = Some(o),
+                (None, Some(o)) => {
+                    *node = Some(o);
+                    return Some(e.value);
+                },
                 (left, right) => {
                     e.left = left;
                     e.right = right;
-                    {
+                    let removed = {
                         let tmp = &mut *e;
                         let succ = get_min_node_mut(&mut tmp.right);
                         mem::swap(&mut tmp.value, &mut succ.as_mut().unwrap().value);
-                        Self::del(succ);
-                    }
+                        Self::del(succ)
+                    };
                     *node = Some(e);
+                    return removed;
                 }
             }
         }
+        None
     }
     
-    fn get_mut(&mut self, key: &T) -> &mut Edge<T,K>
-        where T:Ord
-    {
+    fn get_edge_mut(&mut self, key: &T) -> &mut Edge<T,K> {
         let mut curr: *mut Edge<T,K> = &mut self.root;
+        let cmp = &self.cmp;
         unsafe {
             while let Some(ref mut node) = *curr {
-                match node.cmp_to_key(&key) {
+                match node.cmp_to_key(key, cmp) {
                     Less => curr = &mut node.left,
                     Greater => curr = &mut node.right,
                     Equal => {
@@ -156,12 +387,10 @@ impl<T,K> BST<T,K>
     }
 
 
-    pub fn get(&self, key: &T) -> Option<&K>
-        where T:Ord
-    {
+    pub fn get(&self, key: &T) -> Option<&K> {
         let mut curr = &self.root;
         while let Some(ref node) = curr {
-            match node.cmp_to_key(&key) {
+            match node.cmp_to_key(key, &self.cmp) {
                 Less => curr = &node.left,
                 Greater => curr = &node.right,
                 Equal => {
@@ -172,18 +401,23 @@ impl<T,K> BST<T,K>
         None
     }
 
-    pub fn put(&mut self, key: T, val: K) 
-        where T:Ord
-    {
+    /// Returns a mutable reference to the value stored for `key`
+    pub fn get_mut(&mut self, key: &T) -> Option<&mut K> {
+        self.get_edge_mut(key).as_mut().map(|node| &mut node.value.val)
+    }
+
+    pub fn put(&mut self, key: T, val: K) {
         let to_insert = Node::new(key, val);
         if self.root.is_none() {
             self.root = Some(Box::new(to_insert));
+            self.n += 1;
             return;
         }
         let mut curr: *mut Edge<T,K> = &mut self.root;
+        let cmp = &self.cmp;
         unsafe{
             while let Some(ref mut node) = *curr {
-                match node.cmp(&to_insert) {
+                match node.cmp_to_key(&to_insert.value.key, cmp) {
                     Less => curr = &mut node.left,
                     Greater => curr = &mut node.right,
                     Equal => {
@@ -193,6 +427,7 @@ impl<T,K> BST<T,K>
                 }
             }
             curr.replace(Some(Box::new(to_insert)));
+            self.n += 1;
             return;
         }
     }
@@ -200,6 +435,221 @@ impl<T,K> BST<T,K>
     
 }
 
+/// A Cursor points at one key of a `BST` and steps to the next or previous key in sorted order.
+/// It keeps the path from the root to the current node, so stepping over all *N* keys costs *O(N)* in total,
+/// amortised *O(1)* per step, instead of one *O(log N)* search per key.
+///
+/// Past either end the cursor points at nothing and `key` returns `None`.
+/// From there `move_next` wraps around to the smallest key and `move_prev` to the largest.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::searching::BST::BST;
+///
+/// let mut bst: BST<u8,&str> = BST::new();
+/// for (k, v) in [(4, "val4"), (10, "val10"), (2, "val2"), (3, "val3"), (11, "val11")] {
+///     bst.put(k, v);
+/// }
+///
+/// // Walk the keys after 3 and drop the odd ones on the way
+/// let mut cursor = bst.cursor_at(&3);
+/// cursor.move_next();
+/// while let Some(key) = cursor.key() {
+///     if key % 2 == 1 {
+///         cursor.remove_current();
+///     } else {
+///         cursor.move_next();
+///     }
+/// }
+///
+/// let mut cursor = bst.cursor_back();
+/// assert_eq!(cursor.key(), Some(&10));
+/// cursor.move_prev();
+/// assert_eq!(cursor.value(), Some(&"val4"));
+/// assert_eq!(bst.size(), 4);
+/// ```
+pub struct Cursor<'a,T,K,C = Natural> {
+    tree: &'a mut BST<T,K,C>,
+    // The nodes from the root down to the current one, empty when past the end
+    path: Vec<*mut Node<T,K>>,
+}
+
+impl<'a,T,K,C> Cursor<'a,T,K,C>
+    where C: Comparator<T>
+{
+    /// Returns the key the cursor points at
+    pub fn key(&self) -> Option<&T> {
+        self.path.last().map(|node| unsafe { &(**node).value.key })
+    }
+
+    /// Returns the value the cursor points at
+    pub fn value(&self) -> Option<&K> {
+        self.path.last().map(|node| unsafe { &(**node).value.val })
+    }
+
+    /// Returns a mutable reference to the value the cursor points at
+    pub fn value_mut(&mut self) -> Option<&mut K> {
+        self.path.last().map(|node| unsafe { &mut (**node).value.val })
+    }
+
+    fn push_leftmost(&mut self, mut node: *mut Node<T,K>) {
+        unsafe {
+            loop {
+                self.path.push(node);
+                match (*node).left {
+                    Some(ref mut left) => node = &mut **left,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fn push_rightmost(&mut self, mut node: *mut Node<T,K>) {
+        unsafe {
+            loop {
+                self.path.push(node);
+                match (*node).right {
+                    Some(ref mut right) => node = &mut **right,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fn is_child(edge: &Edge<T,K>, child: *mut Node<T,K>) -> bool {
+        match edge {
+            Some(node) => std::ptr::eq(&**node, child),
+            None => false,
+        }
+    }
+
+    /// Pops the path until the current node is the first ancestor that `child` lies to the left of
+    fn ascend_next(&mut self, mut child: *mut Node<T,K>) {
+        while let Some(&parent) = self.path.last() {
+            if Self::is_child(unsafe { &(*parent).left }, child) {
+                return;
+            }
+            child = self.path.pop().unwrap();
+        }
+    }
+
+    /// Pops the path until the current node is the first ancestor that `child` lies to the right of
+    fn ascend_prev(&mut self, mut child: *mut Node<T,K>) {
+        while let Some(&parent) = self.path.last() {
+            if Self::is_child(unsafe { &(*parent).right }, child) {
+                return;
+            }
+            child = self.path.pop().unwrap();
+        }
+    }
+
+    /// Moves to the next larger key
+    pub fn move_next(&mut self) {
+        let curr = match self.path.last() {
+            Some(&curr) => curr,
+            None => {
+                if let Some(ref mut root) = self.tree.root {
+                    let root: *mut Node<T,K> = &mut **root;
+                    self.push_leftmost(root);
+                }
+                return;
+            },
+        };
+        if let Some(ref mut right) = unsafe { &mut (*curr).right } {
+            let right: *mut Node<T,K> = &mut **right;
+            self.push_leftmost(right);
+            return;
+        }
+        let child = self.path.pop().unwrap();
+        self.ascend_next(child);
+    }
+
+    /// Moves to the next smaller key
+    pub fn move_prev(&mut self) {
+        let curr = match self.path.last() {
+            Some(&curr) => curr,
+            None => {
+                if let Some(ref mut root) = self.tree.root {
+                    let root: *mut Node<T,K> = &mut **root;
+                    self.push_rightmost(root);
+                }
+                return;
+            },
+        };
+        if let Some(ref mut left) = unsafe { &mut (*curr).left } {
+            let left: *mut Node<T,K> = &mut **left;
+            self.push_rightmost(left);
+            return;
+        }
+        let child = self.path.pop().unwrap();
+        self.ascend_prev(child);
+    }
+
+    /// Removes the current key-value pair from the table and moves the cursor to the next larger key
+    pub fn remove_current(&mut self) -> Option<(T,K)> {
+        let curr = *self.path.last()?;
+        let parent = if self.path.len() > 1 { Some(self.path[self.path.len() - 2]) } else { None };
+        unsafe {
+            let is_left = parent.is_some_and(|p| Self::is_child(&(*p).left, curr));
+            let edge: *mut Edge<T,K> = match parent {
+                None => &mut self.tree.root,
+                Some(p) if is_left => &mut (*p).left,
+                Some(p) => &mut (*p).right,
+            };
+            let two_children = (*curr).left.is_some() && (*curr).right.is_some();
+            let only_right = (*curr).left.is_none() && (*curr).right.is_some();
+
+            let removed = BST::<T,K,C>::del(&mut *edge).unwrap();
+            self.tree.n -= 1;
+
+            if two_children {
+                // The successor was swapped into the current node, so the path is still right
+                return Some((removed.key, removed.val));
+            }
+            self.path.pop();
+            if only_right {
+                // The right child took the place of the removed node, the successor is its leftmost node
+                let child: *mut Node<T,K> = &mut **(*edge).as_mut().unwrap();
+                self.push_leftmost(child);
+            } else if !is_left {
+                if let Some(child) = self.path.pop() {
+                    self.ascend_next(child);
+                }
+            }
+            Some((removed.key, removed.val))
+        }
+    }
+}
+
+/// Serializes the table as a sequence of `(key, value)` pairs in ascending key order,
+/// so the serialized form does not depend on the shape of the tree
+#[cfg(feature = "serde")]
+impl<T,K,C> serde::Serialize for BST<T,K,C>
+    where T: serde::Serialize, K: serde::Serialize, C: Comparator<T>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut pairs = Vec::with_capacity(self.n);
+        Self::pairs_of(&self.root, &mut pairs);
+        serializer.collect_seq(pairs)
+    }
+}
+
+/// Deserializes a sequence of `(key, value)` pairs into a balanced table.
+/// The pairs may come in any order, but a key must not appear twice.
+#[cfg(feature = "serde")]
+impl<'de,T,K,C> serde::Deserialize<'de> for BST<T,K,C>
+    where T: serde::Deserialize<'de>, K: serde::Deserialize<'de>, C: Comparator<T> + Default
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cmp = C::default();
+        let mut pairs: Vec<(T,K)> = Vec::deserialize(deserializer)?;
+        pairs.sort_by(|a, b| cmp.compare(&a.0, &b.0));
+        if pairs.windows(2).any(|p| cmp.compare(&p[0].0, &p[1].0) == Equal) {
+            return Err(serde::de::Error::custom("duplicate key in BST"));
+        }
+        Ok(Self::from_sorted_vec_with_comparator(pairs, cmp))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -242,6 +692,166 @@ mod tests {
        assert_eq!(bst.get(&20).unwrap(), &"John");
        assert_eq!(bst.get(&30).unwrap(), &"Doe");
     }
+
+    #[test]
+    fn test_size_and_range() {
+       let mut bst: BST<i32,&str> = BST::new();
+       for (k, v) in [(5, "e"), (2, "b"), (8, "h"), (1, "a"), (3, "c"), (9, "i")] {
+           bst.put(k, v);
+       }
+       bst.put(3, "C");
+       assert_eq!(bst.size(), 6);
+
+       let mut range = bst.range(&2, &8);
+       assert_eq!(range.dequeue(), Some((&2, &"b")));
+       assert_eq!(range.dequeue(), Some((&3, &"C")));
+       assert_eq!(range.dequeue(), Some((&5, &"e")));
+       assert_eq!(range.dequeue(), Some((&8, &"h")));
+       assert_eq!(range.dequeue(), None);
+
+       bst.delete(&4);
+       assert_eq!(bst.size(), 6);
+       bst.delete(&5);
+       bst.delete_min();
+       bst.delete_max();
+       assert_eq!(bst.size(), 3);
+       assert!(!bst.contains(&5));
+       assert!(bst.contains(&8));
+    }
+
+    #[test]
+    fn test_ordered_operations() {
+       let bst: BST<i32,i32> = BST::from_sorted_vec((0..7).map(|i| (i * 10, i)).collect());
+       assert_eq!(bst.size(), 7);
+       assert_eq!(bst.get_root(), Some(&3));
+       assert_eq!(bst.height(), 3);
+       assert_eq!(bst.min(), Some(&0));
+       assert_eq!(bst.max(), Some(&60));
+       assert_eq!(bst.floor(&25), Some(&20));
+       assert_eq!(bst.floor(&-1), None);
+       assert_eq!(bst.ceiling(&25), Some(&30));
+       assert_eq!(bst.ceiling(&60), Some(&60));
+       assert_eq!(bst.ceiling(&61), None);
+
+       let mut keys = bst.keys();
+       for i in 0..7 {
+           assert_eq!(keys.dequeue(), Some(&(i * 10)));
+       }
+       assert!(keys.is_empty());
+    }
+
+    #[test]
+    fn test_case_insensitive_comparator() {
+       let mut bst = BST::with_comparator(|a: &String, b: &String| a.to_lowercase().cmp(&b.to_lowercase()));
+       bst.put("Ferris".to_string(), 1);
+       bst.put("ferris".to_string(), 2);
+       bst.put("Crab".to_string(), 3);
+       assert_eq!(bst.size(), 2);
+       assert_eq!(bst.get(&"FERRIS".to_string()), Some(&2));
+       bst.delete(&"crab".to_string());
+       assert!(!bst.contains(&"Crab".to_string()));
+    }
+
+    #[test]
+    fn test_float_comparator() {
+       let mut bst = BST::with_comparator(|a: &f64, b: &f64| a.total_cmp(b));
+       for k in [2.5, -1.0, 3.75, 0.5] {
+           bst.put(k, ());
+       }
+       assert_eq!(bst.min(), Some(&-1.0));
+       assert_eq!(bst.floor(&3.0), Some(&2.5));
+       assert_eq!(bst.range(&0.0, &3.0).size(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+       let mut bst: BST<i32,&str> = BST::new();
+       for (k, v) in [(1, "a"), (2, "b"), (3, "c"), (4, "d"), (5, "e")] {
+           bst.put(k, v);
+       }
+       let json = serde_json::to_string(&bst).unwrap();
+       assert_eq!(json, r#"[[1,"a"],[2,"b"],[3,"c"],[4,"d"],[5,"e"]]"#);
+
+       // The degenerate input comes back balanced, with the median at the root
+       let back: BST<i32,String> = serde_json::from_str(&json).unwrap();
+       assert_eq!(back.size(), 5);
+       assert_eq!(back.get_root(), Some(&"c".to_string()));
+
+       let unordered: BST<i32,i32> = serde_json::from_str("[[3,30],[1,10],[2,20]]").unwrap();
+       assert_eq!(unordered.get_root(), Some(&20));
+       assert!(serde_json::from_str::<BST<i32,i32>>("[[1,1],[1,2]]").is_err());
+    }
+
+    #[test]
+    fn test_cursor_walk() {
+       let keys = [50, 30, 70, 20, 40, 60, 80, 35, 45, 65];
+       let mut bst: BST<i32,i32> = BST::new();
+       for k in keys {
+           bst.put(k, k * 10);
+       }
+       let mut sorted = keys.to_vec();
+       sorted.sort();
+
+       let mut cursor = bst.cursor_front();
+       for k in &sorted {
+           assert_eq!(cursor.key(), Some(k));
+           assert_eq!(cursor.value(), Some(&(k * 10)));
+           cursor.move_next();
+       }
+       assert_eq!(cursor.key(), None);
+       cursor.move_next();
+       assert_eq!(cursor.key(), Some(&20));
+
+       let mut cursor = bst.cursor_back();
+       for k in sorted.iter().rev() {
+           assert_eq!(cursor.key(), Some(k));
+           cursor.move_prev();
+       }
+       assert_eq!(cursor.key(), None);
+
+       assert_eq!(bst.cursor_at(&41).key(), Some(&45));
+       assert_eq!(bst.cursor_at(&46).key(), Some(&50));
+       assert_eq!(bst.cursor_at(&65).key(), Some(&65));
+       assert_eq!(bst.cursor_at(&81).key(), None);
+       let mut cursor = bst.cursor_at(&0);
+       cursor.move_prev();
+       assert_eq!(cursor.key(), None);
+    }
+
+    #[test]
+    fn test_cursor_remove_every_shape() {
+       // Removing each key in turn covers leaves, single children and two children
+       let keys = [50, 30, 70, 20, 40, 60, 80, 35, 45, 65, 10, 85];
+       for (i, removed) in keys.iter().enumerate() {
+           let mut bst: BST<i32,i32> = BST::new();
+           for k in keys {
+               bst.put(k, k);
+           }
+           let mut cursor = bst.cursor_at(removed);
+           assert_eq!(cursor.remove_current(), Some((*removed, *removed)));
+           let next = keys.iter().filter(|k| *k > removed).min();
+           assert_eq!(cursor.key(), next, "after removing key #{}", i);
+           assert_eq!(bst.size(), keys.len() - 1);
+           assert!(!bst.contains(removed));
+       }
+    }
+
+    #[test]
+    fn test_cursor_remove_all() {
+       let mut bst: BST<i32,i32> = BST::new();
+       for k in [5, 2, 8, 1, 3, 7, 9, 4, 6] {
+           bst.put(k, k);
+       }
+       let mut cursor = bst.cursor_front();
+       let mut removed = Vec::new();
+       while let Some((k, _)) = cursor.remove_current() {
+           removed.push(k);
+       }
+       assert_eq!(removed, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+       assert!(bst.is_empty());
+       assert!(bst.cursor_back().key().is_none());
+    }
     //
     //#[test]
     //fn test_get() {
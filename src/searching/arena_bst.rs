@@ -0,0 +1,404 @@
+use std::cmp::Ordering::*;
+
+const NIL: u32 = u32::MAX;
+
+/// The ArenaBST is an ordered symbol table with the same operations as `BST`, but with every node stored in one `Vec`.
+/// Links between nodes are `u32` indices into that `Vec` instead of `Box` pointers, so a bulk load is a handful of
+/// reallocations rather than one allocation per key, and dropping the tree frees a single buffer.
+///
+/// Deleted slots are put on a free list and handed out again by the next `put`.
+/// After many deletes `compact` renumbers the live nodes in in-order order, which packs the slots and places neighbouring keys next to each other in memory.
+/// `shrink_to_fit` compacts and then releases the unused capacity.
+///
+/// `put`, `get` and `delete` take *O(log N)* on average and *O(N)* in the worst case. `compact` is *O(N)*.
+/// The table can hold at most `u32::MAX` nodes, in the slots `0` to `u32::MAX - 1`, since `u32::MAX` marks a missing link.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::searching::arena_bst::ArenaBST;
+///
+/// let mut bst: ArenaBST<u32, &str> = ArenaBST::with_capacity(4);
+/// bst.put(4, "val4");
+/// bst.put(2, "val2");
+/// bst.put(10, "val10");
+/// bst.delete(&4);
+///
+/// assert_eq!(bst.get(&2), Some(&"val2"));
+/// assert_eq!(bst.get(&4), None);
+///
+/// bst.shrink_to_fit();
+/// assert!(bst.capacity() >= bst.size());
+/// assert_eq!(bst.get(&10), Some(&"val10"));
+/// ```
+pub struct ArenaBST<T,K> {
+    nodes: Vec<Option<Node<T,K>>>,
+    free: Vec<u32>,
+    root: u32,
+    n: usize,
+}
+
+struct Node<T,K> {
+    key: T,
+    val: K,
+    left: u32,
+    right: u32,
+}
+
+impl<T,K> ArenaBST<T,K>
+    where T: Ord
+{
+    /// Creates an empty table
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty table with room for `capacity` nodes before it reallocates
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArenaBST { nodes: Vec::with_capacity(capacity), free: Vec::new(), root: NIL, n: 0 }
+    }
+
+    /// Returns the number of key-value pairs in the table
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Returns true if the table holds no keys
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the number of nodes the arena can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    fn node(&self, i: u32) -> &Node<T,K> {
+        self.nodes[i as usize].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, i: u32) -> &mut Node<T,K> {
+        self.nodes[i as usize].as_mut().unwrap()
+    }
+
+    fn alloc(&mut self, key: T, val: K) -> u32 {
+        let node = Some(Node { key, val, left: NIL, right: NIL });
+        if let Some(i) = self.free.pop() {
+            self.nodes[i as usize] = node;
+            return i;
+        }
+        if self.nodes.len() >= NIL as usize {
+            panic!("ArenaBST is full!");
+        }
+        self.nodes.push(node);
+        (self.nodes.len() - 1) as u32
+    }
+
+    fn release(&mut self, i: u32) -> Node<T,K> {
+        self.free.push(i);
+        self.nodes[i as usize].take().unwrap()
+    }
+
+    fn find(&self, key: &T) -> u32 {
+        let mut curr = self.root;
+        while curr != NIL {
+            let node = self.node(curr);
+            match key.cmp(&node.key) {
+                Less => curr = node.left,
+                Greater => curr = node.right,
+                Equal => return curr,
+            }
+        }
+        NIL
+    }
+
+    /// Returns the value stored for `key`
+    pub fn get(&self, key: &T) -> Option<&K> {
+        match self.find(key) {
+            NIL => None,
+            i => Some(&self.node(i).val),
+        }
+    }
+
+    /// Returns a mutable reference to the value stored for `key`
+    pub fn get_mut(&mut self, key: &T) -> Option<&mut K> {
+        match self.find(key) {
+            NIL => None,
+            i => Some(&mut self.node_mut(i).val),
+        }
+    }
+
+    /// Returns true if the table holds a value for `key`
+    pub fn contains(&self, key: &T) -> bool {
+        self.find(key) != NIL
+    }
+
+    /// Inserts the pair, replacing the value if the key is already present
+    pub fn put(&mut self, key: T, val: K) {
+        if self.root == NIL {
+            self.root = self.alloc(key, val);
+            self.n += 1;
+            return;
+        }
+        let mut curr = self.root;
+        loop {
+            let node = self.node_mut(curr);
+            let ord = key.cmp(&node.key);
+            let next = match ord {
+                Less => node.left,
+                Greater => node.right,
+                Equal => {
+                    node.val = val;
+                    return;
+                }
+            };
+            if next == NIL {
+                let i = self.alloc(key, val);
+                let parent = self.node_mut(curr);
+                if ord == Less {
+                    parent.left = i;
+                } else {
+                    parent.right = i;
+                }
+                self.n += 1;
+                return;
+            }
+            curr = next;
+        }
+    }
+
+    /// Returns the smallest key in the table
+    pub fn min(&self) -> Option<&T> {
+        match self.root {
+            NIL => None,
+            r => Some(&self.node(self.min_of(r)).key),
+        }
+    }
+
+    /// Returns the largest key in the table
+    pub fn max(&self) -> Option<&T> {
+        let mut curr = self.root;
+        if curr == NIL {
+            return None;
+        }
+        while self.node(curr).right != NIL {
+            curr = self.node(curr).right;
+        }
+        Some(&self.node(curr).key)
+    }
+
+    fn min_of(&self, mut x: u32) -> u32 {
+        while self.node(x).left != NIL {
+            x = self.node(x).left;
+        }
+        x
+    }
+
+    /// Removes the smallest key and its value
+    pub fn delete_min(&mut self) {
+        if self.root != NIL {
+            let (root, removed) = self.delete_min_of(self.root);
+            self.root = root;
+            self.release(removed);
+            self.n -= 1;
+        }
+    }
+
+    /// Unlinks the minimum of the subtree at `x`, returning the new subtree root and the unlinked slot
+    fn delete_min_of(&mut self, x: u32) -> (u32, u32) {
+        let mut parent = NIL;
+        let mut curr = x;
+        while self.node(curr).left != NIL {
+            parent = curr;
+            curr = self.node(curr).left;
+        }
+        let right = self.node(curr).right;
+        if parent == NIL {
+            return (right, curr);
+        }
+        self.node_mut(parent).left = right;
+        (x, curr)
+    }
+
+    /// Removes `key` and its value from the table, with Hibbard deletion
+    pub fn delete(&mut self, key: &T) {
+        let mut parent = NIL;
+        let mut side = Equal;
+        let mut curr = self.root;
+        while curr != NIL {
+            let node = self.node(curr);
+            let ord = key.cmp(&node.key);
+            let next = match ord {
+                Less => node.left,
+                Greater => node.right,
+                Equal => break,
+            };
+            side = ord;
+            parent = curr;
+            curr = next;
+        }
+        if curr == NIL {
+            return;
+        }
+
+        let t = self.release(curr);
+        self.n -= 1;
+        let replacement = if t.right == NIL {
+            t.left
+        } else if t.left == NIL {
+            t.right
+        } else {
+            let (right, succ) = self.delete_min_of(t.right);
+            let node = self.node_mut(succ);
+            node.right = right;
+            node.left = t.left;
+            succ
+        };
+        if parent == NIL {
+            self.root = replacement;
+        } else if side == Less {
+            self.node_mut(parent).left = replacement;
+        } else {
+            self.node_mut(parent).right = replacement;
+        }
+    }
+
+    /// Returns the slots of the live nodes in in-order order
+    fn in_order(&self) -> Vec<u32> {
+        let mut order = Vec::with_capacity(self.n);
+        let mut stack = Vec::new();
+        let mut curr = self.root;
+        while curr != NIL || !stack.is_empty() {
+            while curr != NIL {
+                stack.push(curr);
+                curr = self.node(curr).left;
+            }
+            curr = stack.pop().unwrap();
+            order.push(curr);
+            curr = self.node(curr).right;
+        }
+        order
+    }
+
+    /// Renumbers the live nodes in in-order order and drops the free list.
+    /// Afterwards the smallest key lives in slot 0 and the arena holds no free slots.
+    pub fn compact(&mut self) {
+        let order = self.in_order();
+        let mut renumber = vec![NIL; self.nodes.len()];
+        for (new, old) in order.iter().enumerate() {
+            renumber[*old as usize] = new as u32;
+        }
+        let map = |i: u32| if i == NIL { NIL } else { renumber[i as usize] };
+
+        let mut nodes = Vec::with_capacity(self.nodes.capacity());
+        for old in &order {
+            let mut node = self.nodes[*old as usize].take().unwrap();
+            node.left = map(node.left);
+            node.right = map(node.right);
+            nodes.push(Some(node));
+        }
+        self.root = map(self.root);
+        self.nodes = nodes;
+        self.free.clear();
+    }
+
+    /// Compacts the arena and releases all capacity beyond the live nodes
+    pub fn shrink_to_fit(&mut self) {
+        self.compact();
+        self.nodes.shrink_to_fit();
+        self.free.shrink_to_fit();
+    }
+}
+
+impl<T,K> Default for ArenaBST<T,K>
+    where T: Ord
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::ArenaBST;
+
+    #[test]
+    fn test_put_get() {
+        let mut bst: ArenaBST<i32, &str> = ArenaBST::new();
+        bst.put(24, "Ferris");
+        bst.put(20, "John");
+        bst.put(25, "Jane");
+        bst.put(20, "Doe");
+        assert_eq!(bst.size(), 3);
+        assert_eq!(bst.get(&20), Some(&"Doe"));
+        assert_eq!(bst.get(&25), Some(&"Jane"));
+        assert_eq!(bst.min(), Some(&20));
+        assert_eq!(bst.max(), Some(&25));
+        assert_eq!(bst.get(&1), None);
+    }
+
+    #[test]
+    fn test_delete_reuses_slots() {
+        let mut bst: ArenaBST<i32, i32> = ArenaBST::new();
+        for k in [23, 20, 25, 30, 24, 21] {
+            bst.put(k, k * 10);
+        }
+        bst.delete(&23);
+        bst.delete(&99);
+        bst.delete_min();
+        assert_eq!(bst.size(), 4);
+        assert_eq!(bst.get(&23), None);
+        assert_eq!(bst.get(&20), None);
+        for k in [21, 24, 25, 30] {
+            assert_eq!(bst.get(&k), Some(&(k * 10)));
+        }
+
+        // Both freed slots are handed out again before the arena grows
+        bst.put(1, 10);
+        bst.put(2, 20);
+        assert_eq!(bst.nodes.len(), 6);
+        assert_eq!(bst.min(), Some(&1));
+    }
+
+    #[test]
+    fn test_compact_renumbers_in_order() {
+        let mut bst: ArenaBST<i32, i32> = ArenaBST::new();
+        for k in [50, 30, 70, 20, 40, 60, 80, 10] {
+            bst.put(k, k);
+        }
+        bst.delete(&30);
+        bst.delete(&80);
+        bst.shrink_to_fit();
+
+        assert_eq!(bst.nodes.len(), 6);
+        assert!(bst.free.is_empty());
+        let keys: Vec<i32> = bst.nodes.iter().map(|n| n.as_ref().unwrap().key).collect();
+        assert_eq!(keys, vec![10, 20, 40, 50, 60, 70]);
+        for k in keys {
+            assert_eq!(bst.get(&k), Some(&k));
+        }
+        bst.put(30, 30);
+        assert_eq!(bst.get(&30), Some(&30));
+    }
+
+    #[test]
+    fn test_delete_on_a_degenerate_tree() {
+        // Sorted inserts make a chain as deep as the table, deleting from it must not recurse per level
+        let handle = thread::Builder::new().stack_size(64 * 1024).spawn(|| {
+            let mut bst: ArenaBST<u32, u32> = ArenaBST::new();
+            for k in 0..5000 {
+                bst.put(k, k);
+            }
+            bst.delete(&4999);
+            bst.delete(&2500);
+            bst.delete_min();
+            bst.put(0, 0);
+            for k in (0..5000).rev() {
+                bst.delete(&k);
+            }
+            bst.is_empty()
+        }).unwrap();
+        assert!(handle.join().unwrap());
+    }
+}
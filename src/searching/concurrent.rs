@@ -0,0 +1,486 @@
+use std::{cmp::Ordering::{self, *}, collections::hash_map::DefaultHasher, hash::{Hash, Hasher}, sync::{Arc, Mutex}};
+
+use arc_swap::ArcSwap;
+
+const DEFAULT_SHARDS: usize = 16;
+
+type Link<T,K> = Option<Arc<Node<T,K>>>;
+
+/// A node of a shard. Nodes are never changed once they are published, a write makes new copies instead.
+struct Node<T,K> {
+    key: T,
+    val: K,
+    left: Link<T,K>,
+    right: Link<T,K>,
+}
+
+impl<T,K> Drop for Node<T,K> {
+    /// Frees the nodes no other tree shares one at a time, so a long chain does not overflow the stack
+    fn drop(&mut self) {
+        let mut stack: Vec<Arc<Node<T,K>>> = self.left.take().into_iter().chain(self.right.take()).collect();
+        while let Some(link) = stack.pop() {
+            if let Ok(mut node) = Arc::try_unwrap(link) {
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+/// The root of one shard and the number of keys below it
+struct Root<T,K> {
+    link: Link<T,K>,
+    n: usize,
+}
+
+impl<T,K> Clone for Root<T,K> {
+    fn clone(&self) -> Self {
+        Root { link: self.link.clone(), n: self.n }
+    }
+}
+
+/// ConcurrentBST is an ordered symbol table that can be shared between threads without an outer `Mutex`.
+/// The keys are spread over a fixed number of shards by their hash, and every shard is a binary search tree.
+///
+/// The trees are never changed in place. A write copies the nodes on the path to its key and shares every other
+/// node with the old tree, then publishes the new roots of all shards with a single atomic pointer swap.
+/// Readers never take a lock: `get`, `range` and `snapshot` load the current roots with one atomic operation,
+/// so a range sees the whole table, across every shard, as it was at one point in time, however long it runs
+/// and however many writers are busy meanwhile. A reader never makes a writer wait either.
+///
+/// Writers to the same shard are serialized by a `Mutex` of that shard. Writers to different shards build their
+/// new trees in parallel, and only repeat the cheap swap of the list of roots if another writer swapped it first.
+///
+/// `put` and `delete` copy *O(log N)* nodes on average and `get` takes *O(log N)* time, as in `BST`.
+/// `range` is *O(S log N + M S)* for *S* shards and *M* matching keys.
+///
+/// # Examples
+/// ```
+/// use std::{sync::Arc, thread};
+/// use itualgs_rs::searching::concurrent::ConcurrentBST;
+///
+/// let table: Arc<ConcurrentBST<u32, &str>> = Arc::new(ConcurrentBST::new());
+///
+/// let handles: Vec<_> = (0..4).map(|i| {
+///     let table = Arc::clone(&table);
+///     thread::spawn(move || table.put(i, "Ferris"))
+/// }).collect();
+/// for h in handles {
+///     h.join().unwrap();
+/// }
+///
+/// assert_eq!(table.size(), 4);
+/// assert_eq!(table.get(&2), Some("Ferris"));
+/// assert_eq!(table.range(&1, &2), vec![(1, "Ferris"), (2, "Ferris")]);
+///
+/// // A snapshot keeps the table as it was when it was taken
+/// let snapshot = table.snapshot();
+/// table.delete(&1);
+/// assert_eq!(snapshot.get(&1), Some(&"Ferris"));
+/// assert_eq!(table.get(&1), None);
+/// ```
+pub struct ConcurrentBST<T,K> {
+    writers: Vec<Mutex<()>>,
+    roots: ArcSwap<Vec<Root<T,K>>>,
+}
+
+/// A read-only view of a `ConcurrentBST` at the moment `ConcurrentBST::snapshot` was called.
+/// Later writes to the table are not visible through it.
+pub struct Snapshot<T,K> {
+    roots: Arc<Vec<Root<T,K>>>,
+}
+
+impl<T,K> ConcurrentBST<T,K>
+    where T: Ord + Hash + Clone + Send + Sync, K: Clone + Send + Sync
+{
+    /// Creates an empty table with the default number of shards
+    pub fn new() -> Self {
+        Self::with_shards(DEFAULT_SHARDS)
+    }
+
+    /// Creates an empty table with `shards` shards, at least one is always created
+    pub fn with_shards(shards: usize) -> Self {
+        let shards = shards.max(1);
+        ConcurrentBST {
+            writers: (0..shards).map(|_| Mutex::new(())).collect(),
+            roots: ArcSwap::from_pointee(vec![Root { link: None, n: 0 }; shards]),
+        }
+    }
+
+    /// Replaces the root of shard `i`. The caller holds the writer lock of that shard,
+    /// so no other writer changes the slot in between.
+    fn publish(&self, i: usize, root: Root<T,K>) {
+        self.roots.rcu(|roots| {
+            let mut roots = Vec::clone(roots);
+            roots[i] = root.clone();
+            roots
+        });
+    }
+
+    /// Inserts the pair, replacing the value if the key is already present
+    pub fn put(&self, key: T, val: K) {
+        let i = shard_of(&key, self.writers.len());
+        let _writer = self.writers[i].lock().unwrap();
+        let root = {
+            let roots = self.roots.load();
+            let (link, added) = put(&roots[i].link, key, val);
+            Root { link, n: roots[i].n + added as usize }
+        };
+        self.publish(i, root);
+    }
+
+    /// Removes `key` and its value from the table
+    pub fn delete(&self, key: &T) {
+        let i = shard_of(key, self.writers.len());
+        let _writer = self.writers[i].lock().unwrap();
+        let root = {
+            let roots = self.roots.load();
+            match delete(&roots[i].link, key) {
+                Some(link) => Root { link, n: roots[i].n - 1 },
+                None => return,
+            }
+        };
+        self.publish(i, root);
+    }
+
+    /// Returns a copy of the value stored for `key`
+    pub fn get(&self, key: &T) -> Option<K> {
+        let roots = self.roots.load();
+        get(&roots[shard_of(key, roots.len())].link, key).cloned()
+    }
+
+    /// Returns true if the table holds a value for `key`
+    pub fn contains(&self, key: &T) -> bool {
+        let roots = self.roots.load();
+        get(&roots[shard_of(key, roots.len())].link, key).is_some()
+    }
+
+    /// Returns the number of key-value pairs across all shards
+    pub fn size(&self) -> usize {
+        self.roots.load().iter().map(|r| r.n).sum()
+    }
+
+    /// Returns true if no shard holds any key
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Returns a read-only view of the whole table as it is now, without taking any lock
+    pub fn snapshot(&self) -> Snapshot<T,K> {
+        Snapshot { roots: self.roots.load_full() }
+    }
+
+    /// Returns every pair with `lo <= key <= hi` in ascending key order, all read from one snapshot
+    pub fn range(&self, lo: &T, hi: &T) -> Vec<(T, K)> {
+        self.snapshot().range(lo, hi).into_iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl<T,K> Default for ConcurrentBST<T,K>
+    where T: Ord + Hash + Clone + Send + Sync, K: Clone + Send + Sync
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Hash, K> Snapshot<T,K> {
+    /// Returns the value stored for `key` when the snapshot was taken
+    pub fn get(&self, key: &T) -> Option<&K> {
+        get(&self.roots[shard_of(key, self.roots.len())].link, key)
+    }
+
+    /// Returns true if the table held a value for `key`
+    pub fn contains(&self, key: &T) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the number of key-value pairs
+    pub fn size(&self) -> usize {
+        self.roots.iter().map(|r| r.n).sum()
+    }
+
+    /// Returns true if the table held no keys
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Returns every pair with `lo <= key <= hi` in ascending key order
+    pub fn range(&self, lo: &T, hi: &T) -> Vec<(&T, &K)> {
+        let mut runs: Vec<Vec<(&T, &K)>> = Vec::with_capacity(self.roots.len());
+        for root in self.roots.iter() {
+            let mut run = Vec::new();
+            range_of(&root.link, lo, hi, &mut run);
+            run.reverse();
+            runs.push(run);
+        }
+
+        // Every run is sorted descending, so the smallest key of a run is at its end
+        let mut merged = Vec::with_capacity(runs.iter().map(|r| r.len()).sum());
+        loop {
+            let mut smallest: Option<usize> = None;
+            for (i, run) in runs.iter().enumerate() {
+                if let Some((k, _)) = run.last() {
+                    match smallest {
+                        Some(j) if runs[j].last().unwrap().0 <= *k => (),
+                        _ => smallest = Some(i),
+                    }
+                }
+            }
+            match smallest {
+                Some(i) => merged.push(runs[i].pop().unwrap()),
+                None => break,
+            }
+        }
+        merged
+    }
+}
+
+fn shard_of<T: Hash>(key: &T, shards: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish() as usize % shards
+}
+
+fn get<'a, T: Ord, K>(mut link: &'a Link<T,K>, key: &T) -> Option<&'a K> {
+    while let Some(node) = link {
+        match key.cmp(&node.key) {
+            Less => link = &node.left,
+            Greater => link = &node.right,
+            Equal => return Some(&node.val),
+        }
+    }
+    None
+}
+
+/// Copies the nodes on `path`, bottom up, so that the last one points to `child` instead of its old subtree.
+/// Every node is paired with the side the path leaves it by.
+fn rebuild<T: Clone, K: Clone>(path: Vec<(&Arc<Node<T,K>>, Ordering)>, mut child: Link<T,K>) -> Link<T,K> {
+    for (node, side) in path.into_iter().rev() {
+        let (left, right) = if side == Less {
+            (child, node.right.clone())
+        } else {
+            (node.left.clone(), child)
+        };
+        child = Some(Arc::new(Node { key: node.key.clone(), val: node.val.clone(), left, right }));
+    }
+    child
+}
+
+/// Returns a new root with the pair inserted, and whether the key is new
+fn put<T: Ord + Clone, K: Clone>(root: &Link<T,K>, key: T, val: K) -> (Link<T,K>, bool) {
+    let mut path = Vec::new();
+    let mut link = root;
+    loop {
+        match link {
+            None => {
+                let leaf = Node { key, val, left: None, right: None };
+                return (rebuild(path, Some(Arc::new(leaf))), true);
+            }
+            Some(node) => match key.cmp(&node.key) {
+                Less => {
+                    path.push((node, Less));
+                    link = &node.left;
+                }
+                Greater => {
+                    path.push((node, Greater));
+                    link = &node.right;
+                }
+                Equal => {
+                    let replaced = Node { key, val, left: node.left.clone(), right: node.right.clone() };
+                    return (rebuild(path, Some(Arc::new(replaced))), false);
+                }
+            },
+        }
+    }
+}
+
+/// Returns a new root without `key`, or None if the key is not in the tree
+fn delete<T: Ord + Clone, K: Clone>(root: &Link<T,K>, key: &T) -> Option<Link<T,K>> {
+    let mut path = Vec::new();
+    let mut link = root;
+    let node = loop {
+        let node = link.as_ref()?;
+        match key.cmp(&node.key) {
+            Less => {
+                path.push((node, Less));
+                link = &node.left;
+            }
+            Greater => {
+                path.push((node, Greater));
+                link = &node.right;
+            }
+            Equal => break node,
+        }
+    };
+    let replacement = match (&node.left, &node.right) {
+        (None, right) => right.clone(),
+        (left, None) => left.clone(),
+        (left, Some(right)) => {
+            // Hibbard deletion: the smallest key of the right subtree takes the place of the node
+            let mut right_path = Vec::new();
+            let mut min = right;
+            while let Some(l) = &min.left {
+                right_path.push((min, Less));
+                min = l;
+            }
+            let right = rebuild(right_path, min.right.clone());
+            Some(Arc::new(Node { key: min.key.clone(), val: min.val.clone(), left: left.clone(), right }))
+        }
+    };
+    Some(rebuild(path, replacement))
+}
+
+/// Appends every pair with `lo <= key <= hi` in ascending key order
+fn range_of<'a, T: Ord, K>(root: &'a Link<T,K>, lo: &T, hi: &T, out: &mut Vec<(&'a T, &'a K)>) {
+    let mut stack: Vec<&Arc<Node<T,K>>> = Vec::new();
+    let mut link = root;
+    loop {
+        while let Some(node) = link {
+            if node.key < *lo {
+                // The node and its whole left subtree are below the range
+                link = &node.right;
+            } else {
+                stack.push(node);
+                link = &node.left;
+            }
+        }
+        match stack.pop() {
+            Some(node) if node.key <= *hi => {
+                out.push((&node.key, &node.val));
+                link = &node.right;
+            }
+            _ => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::{AtomicBool, Ordering}, thread};
+
+    use super::ConcurrentBST;
+
+    #[test]
+    fn test_put_get_delete() {
+        let table: ConcurrentBST<i32, &str> = ConcurrentBST::with_shards(4);
+        table.put(24, "Ferris");
+        table.put(20, "John");
+        table.put(24, "Jane");
+        assert_eq!(table.size(), 2);
+        assert_eq!(table.get(&24), Some("Jane"));
+        table.delete(&24);
+        table.delete(&24);
+        assert!(!table.contains(&24));
+        assert_eq!(table.size(), 1);
+    }
+
+    #[test]
+    fn test_delete_every_shape() {
+        let table: ConcurrentBST<i32, i32> = ConcurrentBST::with_shards(1);
+        for k in [50, 30, 70, 20, 40, 60, 80, 35, 45, 65] {
+            table.put(k, -k);
+        }
+        let before = table.snapshot();
+        // A leaf, a node with one child, a node with two children and the root
+        for k in [20, 60, 30, 50] {
+            table.delete(&k);
+        }
+        let keys: Vec<i32> = table.range(&0, &100).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![35, 40, 45, 65, 70, 80]);
+        assert_eq!(table.size(), 6);
+        assert_eq!(before.size(), 10);
+        assert_eq!(before.range(&0, &100).len(), 10);
+        assert_eq!(before.get(&30), Some(&-30));
+    }
+
+    #[test]
+    fn test_range_is_sorted_across_shards() {
+        let table: ConcurrentBST<i32, i32> = ConcurrentBST::with_shards(7);
+        for i in (0..100).rev() {
+            table.put(i, i * 2);
+        }
+        let range = table.range(&10, &19);
+        let expected: Vec<(i32, i32)> = (10..20).map(|i| (i, i * 2)).collect();
+        assert_eq!(range, expected);
+        assert!(table.range(&19, &10).is_empty());
+    }
+
+    #[test]
+    fn test_readers_do_not_wait_for_writers() {
+        let table: ConcurrentBST<i32, i32> = ConcurrentBST::with_shards(3);
+        for i in 0..10 {
+            table.put(i, i);
+        }
+        // Holding every writer lock stops writers, but not readers
+        let _writers: Vec<_> = table.writers.iter().map(|w| w.lock().unwrap()).collect();
+        assert_eq!(table.range(&0, &9).len(), 10);
+        assert_eq!(table.get(&3), Some(3));
+        assert_eq!(table.size(), 10);
+    }
+
+    #[test]
+    fn test_range_is_one_point_in_time() {
+        // Keys are put in ascending order, so any consistent view holds exactly 0..m for some m,
+        // even though consecutive keys live in different shards
+        let table: ConcurrentBST<usize, usize> = ConcurrentBST::with_shards(8);
+        let done = AtomicBool::new(false);
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..3000 {
+                    table.put(i, i);
+                }
+                done.store(true, Ordering::SeqCst);
+            });
+            for _ in 0..3 {
+                s.spawn(|| {
+                    while !done.load(Ordering::SeqCst) {
+                        let keys: Vec<usize> = table.range(&0, &3000).into_iter().map(|(k, _)| k).collect();
+                        assert!(keys.iter().enumerate().all(|(i, k)| i == *k));
+                    }
+                });
+            }
+        });
+        assert_eq!(table.size(), 3000);
+    }
+
+    #[test]
+    fn test_many_threads() {
+        let table: ConcurrentBST<usize, usize> = ConcurrentBST::new();
+        let writers = 8;
+        let per_writer = 2000;
+
+        thread::scope(|s| {
+            for w in 0..writers {
+                let table = &table;
+                s.spawn(move || {
+                    for i in 0..per_writer {
+                        table.put(i * writers + w, w);
+                    }
+                    // Remove every odd key this writer owns
+                    for i in (1..per_writer).step_by(2) {
+                        table.delete(&(i * writers + w));
+                    }
+                });
+            }
+            for _ in 0..4 {
+                let table = &table;
+                s.spawn(move || {
+                    for _ in 0..50 {
+                        let snapshot = table.range(&0, &(writers * per_writer));
+                        assert!(snapshot.windows(2).all(|p| p[0].0 < p[1].0));
+                        for (k, v) in snapshot {
+                            assert_eq!(k % writers, v);
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(table.size(), writers * per_writer / 2);
+        for w in 0..writers {
+            assert_eq!(table.get(&w), Some(w));
+            assert!(!table.contains(&(writers + w)));
+        }
+    }
+}
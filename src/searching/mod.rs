@@ -1 +1,5 @@
-pub mod BST;
\ No newline at end of file
+pub mod BST;
+pub mod concurrent;
+pub mod arena_bst;
+pub mod set;
+pub mod multimap;
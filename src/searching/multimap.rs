@@ -0,0 +1,160 @@
+use crate::fundamentals::queue::Queue;
+
+use super::BST::BST;
+
+/// BSTMultiMap is an ordered symbol table where a key can hold any number of values.
+/// Where `BST::put` replaces the value of an existing key, `put` here appends to the values of that key,
+/// and `get_all` hands them back in the order they were inserted.
+///
+/// This is the usual shape of an inverted index, where every word maps to the list of documents it occurs in.
+///
+/// `put` and `get_all` take *O(log N)* on average for *N* distinct keys. `remove_one` is linear in the number of values under the key.
+/// `len` counts values, not keys, use `key_count` for the number of distinct keys.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::searching::multimap::BSTMultiMap;
+///
+/// let mut index = BSTMultiMap::new();
+/// index.put("ferris", 1);
+/// index.put("crab", 1);
+/// index.put("ferris", 7);
+/// index.put("ferris", 3);
+///
+/// let docs: Vec<&u32> = index.get_all(&"ferris").collect();
+/// assert_eq!(docs, vec![&1, &7, &3]);
+/// assert_eq!(index.len(), 4);
+/// assert_eq!(index.key_count(), 2);
+///
+/// index.remove_one(&"ferris", &7);
+/// let docs: Vec<&u32> = index.get_all(&"ferris").collect();
+/// assert_eq!(docs, vec![&1, &3]);
+/// ```
+pub struct BSTMultiMap<T,K> {
+    tree: BST<T,Vec<K>>,
+    n: usize,
+}
+
+impl<T,K> BSTMultiMap<T,K>
+    where T: Ord
+{
+    /// Creates an empty multimap
+    pub fn new() -> Self {
+        BSTMultiMap { tree: BST::new(), n: 0 }
+    }
+
+    /// Adds `val` after the values already stored for `key`
+    pub fn put(&mut self, key: T, val: K) {
+        match self.tree.get_mut(&key) {
+            Some(values) => values.push(val),
+            None => self.tree.put(key, vec![val]),
+        }
+        self.n += 1;
+    }
+
+    /// Returns the values stored for `key` in insertion order, empty if the key is missing
+    pub fn get_all(&self, key: &T) -> impl Iterator<Item = &K> {
+        self.tree.get(key).into_iter().flatten()
+    }
+
+    /// Returns true if at least one value is stored for `key`
+    pub fn contains_key(&self, key: &T) -> bool {
+        self.tree.contains(key)
+    }
+
+    /// Removes the first value under `key` that equals `val`, returns true if one was removed
+    pub fn remove_one(&mut self, key: &T, val: &K) -> bool
+        where K: PartialEq
+    {
+        let values = match self.tree.get_mut(key) {
+            Some(values) => values,
+            None => return false,
+        };
+        let pos = match values.iter().position(|v| v == val) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        values.remove(pos);
+        if values.is_empty() {
+            self.tree.delete(key);
+        }
+        self.n -= 1;
+        true
+    }
+
+    /// Removes `key` together with every value stored for it, returns how many values were removed
+    pub fn remove_all(&mut self, key: &T) -> usize {
+        let removed = self.tree.get(key).map_or(0, |values| values.len());
+        if removed > 0 {
+            self.tree.delete(key);
+            self.n -= removed;
+        }
+        removed
+    }
+
+    /// Returns the number of values across all keys
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of distinct keys
+    pub fn key_count(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Returns true if no values are stored
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns every distinct key in ascending order
+    pub fn keys(&self) -> Queue<&T> {
+        self.tree.keys()
+    }
+}
+
+impl<T,K> Default for BSTMultiMap<T,K>
+    where T: Ord
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BSTMultiMap;
+
+    #[test]
+    fn test_values_keep_insertion_order() {
+        let mut map = BSTMultiMap::new();
+        for (k, v) in [(2, 'b'), (1, 'a'), (2, 'c'), (2, 'b')] {
+            map.put(k, v);
+        }
+        assert_eq!(map.get_all(&2).collect::<String>(), "bcb");
+        assert_eq!(map.get_all(&1).collect::<String>(), "a");
+        assert_eq!(map.get_all(&3).count(), 0);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.key_count(), 2);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map = BSTMultiMap::new();
+        for (k, v) in [(2, 'b'), (1, 'a'), (2, 'c'), (2, 'b')] {
+            map.put(k, v);
+        }
+        assert!(map.remove_one(&2, &'b'));
+        assert_eq!(map.get_all(&2).collect::<String>(), "cb");
+        assert!(!map.remove_one(&2, &'z'));
+        assert!(!map.remove_one(&5, &'a'));
+
+        assert!(map.remove_one(&1, &'a'));
+        assert!(!map.contains_key(&1));
+
+        assert_eq!(map.remove_all(&2), 2);
+        assert_eq!(map.remove_all(&2), 0);
+        assert!(map.is_empty());
+        assert_eq!(map.key_count(), 0);
+    }
+}
@@ -0,0 +1,224 @@
+use std::cmp::Ordering::*;
+
+use crate::fundamentals::queue::Queue;
+
+use super::BST::BST;
+
+/// BSTSet is an ordered set of keys backed by a `BST` without values.
+/// It supports the operations `insert`, `remove`, `contains`, `min`, `max`, `floor` and `ceiling`,
+/// which all have the running time of the underlying `BST`, *O(log N)* on average.
+///
+/// The set algebra operations `union`, `intersection`, `difference` and `symmetric_difference`
+/// walk both sets in order at the same time and merge the two sorted key sequences, like the merge step of merge sort.
+/// The result is built as a balanced tree directly from the merged keys, so each operation is *O(N + M)*.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::searching::set::BSTSet;
+///
+/// let mut a = BSTSet::new();
+/// let mut b = BSTSet::new();
+/// for k in [1, 3, 5, 7] {
+///     a.insert(k);
+/// }
+/// for k in [3, 4, 5] {
+///     b.insert(k);
+/// }
+///
+/// let intersection = a.intersection(&b);
+/// let mut both = intersection.keys();
+/// assert_eq!(both.dequeue(), Some(&3));
+/// assert_eq!(both.dequeue(), Some(&5));
+/// assert_eq!(both.dequeue(), None);
+///
+/// assert_eq!(a.union(&b).size(), 5);
+/// assert_eq!(a.floor(&4), Some(&3));
+/// ```
+pub struct BSTSet<T> {
+    tree: BST<T,()>,
+}
+
+impl<T> BSTSet<T>
+    where T: Ord
+{
+    /// Creates an empty set
+    pub fn new() -> Self {
+        BSTSet { tree: BST::new() }
+    }
+
+    /// Adds `key` to the set, does nothing if it is already present
+    pub fn insert(&mut self, key: T) {
+        self.tree.put(key, ());
+    }
+
+    /// Removes `key` from the set
+    pub fn remove(&mut self, key: &T) {
+        self.tree.delete(key);
+    }
+
+    /// Returns true if `key` is in the set
+    pub fn contains(&self, key: &T) -> bool {
+        self.tree.contains(key)
+    }
+
+    /// Returns the number of keys in the set
+    pub fn size(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Returns true if the set holds no keys
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns the smallest key in the set
+    pub fn min(&self) -> Option<&T> {
+        self.tree.min()
+    }
+
+    /// Returns the largest key in the set
+    pub fn max(&self) -> Option<&T> {
+        self.tree.max()
+    }
+
+    /// Returns the largest key less than or equal to `key`
+    pub fn floor(&self, key: &T) -> Option<&T> {
+        self.tree.floor(key)
+    }
+
+    /// Returns the smallest key greater than or equal to `key`
+    pub fn ceiling(&self, key: &T) -> Option<&T> {
+        self.tree.ceiling(key)
+    }
+
+    /// Returns every key in ascending order
+    pub fn keys(&self) -> Queue<&T> {
+        self.tree.keys()
+    }
+
+    /// Returns the keys that are in `self`, in `other` or in both
+    pub fn union(&self, other: &BSTSet<T>) -> BSTSet<T>
+        where T: Clone
+    {
+        self.merge(other, true, true, true)
+    }
+
+    /// Returns the keys that are in both `self` and `other`
+    pub fn intersection(&self, other: &BSTSet<T>) -> BSTSet<T>
+        where T: Clone
+    {
+        self.merge(other, false, true, false)
+    }
+
+    /// Returns the keys that are in `self` but not in `other`
+    pub fn difference(&self, other: &BSTSet<T>) -> BSTSet<T>
+        where T: Clone
+    {
+        self.merge(other, true, false, false)
+    }
+
+    /// Returns the keys that are in exactly one of `self` and `other`
+    pub fn symmetric_difference(&self, other: &BSTSet<T>) -> BSTSet<T>
+        where T: Clone
+    {
+        self.merge(other, true, false, true)
+    }
+
+    /// Merges the in-order walks of both sets. The flags pick which keys are kept:
+    /// keys only in `self`, keys in both, and keys only in `other`.
+    fn merge(&self, other: &BSTSet<T>, only_self: bool, both: bool, only_other: bool) -> BSTSet<T>
+        where T: Clone
+    {
+        let mut a = self.keys();
+        let mut b = other.keys();
+        let mut merged: Vec<(T,())> = Vec::new();
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (None, None) => break,
+                (Some(_), None) => (Less, a.dequeue().unwrap()),
+                (None, Some(_)) => (Greater, b.dequeue().unwrap()),
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    Less => (Less, a.dequeue().unwrap()),
+                    Greater => (Greater, b.dequeue().unwrap()),
+                    Equal => {
+                        b.dequeue();
+                        (Equal, a.dequeue().unwrap())
+                    },
+                },
+            };
+            let keep = match next.0 {
+                Less => only_self,
+                Equal => both,
+                Greater => only_other,
+            };
+            if keep {
+                merged.push((next.1.clone(), ()));
+            }
+        }
+        BSTSet { tree: BST::from_sorted_vec(merged) }
+    }
+}
+
+impl<T> Default for BSTSet<T>
+    where T: Ord
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BSTSet;
+
+    fn set_of(keys: &[i32]) -> BSTSet<i32> {
+        let mut set = BSTSet::new();
+        for k in keys {
+            set.insert(*k);
+        }
+        set
+    }
+
+    fn to_vec(set: &BSTSet<i32>) -> Vec<i32> {
+        let mut keys = set.keys();
+        let mut v = Vec::new();
+        while let Some(k) = keys.dequeue() {
+            v.push(*k);
+        }
+        v
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = set_of(&[5, 2, 8, 2]);
+        assert_eq!(set.size(), 3);
+        assert!(set.contains(&2));
+        set.remove(&2);
+        assert!(!set.contains(&2));
+        assert_eq!(set.min(), Some(&5));
+        assert_eq!(set.max(), Some(&8));
+        assert_eq!(set.ceiling(&6), Some(&8));
+        assert_eq!(set.floor(&4), None);
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set_of(&[1, 3, 5, 7, 9]);
+        let b = set_of(&[0, 3, 4, 9, 10]);
+        assert_eq!(to_vec(&a.union(&b)), vec![0, 1, 3, 4, 5, 7, 9, 10]);
+        assert_eq!(to_vec(&a.intersection(&b)), vec![3, 9]);
+        assert_eq!(to_vec(&a.difference(&b)), vec![1, 5, 7]);
+        assert_eq!(to_vec(&b.difference(&a)), vec![0, 4, 10]);
+        assert_eq!(to_vec(&a.symmetric_difference(&b)), vec![0, 1, 4, 5, 7, 10]);
+    }
+
+    #[test]
+    fn test_set_algebra_with_empty() {
+        let a = set_of(&[1, 2]);
+        let empty = BSTSet::new();
+        assert_eq!(to_vec(&a.union(&empty)), vec![1, 2]);
+        assert!(a.intersection(&empty).is_empty());
+        assert_eq!(to_vec(&empty.symmetric_difference(&a)), vec![1, 2]);
+    }
+}
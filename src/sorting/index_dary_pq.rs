@@ -0,0 +1,289 @@
+use std::cmp::Ordering;
+
+use crate::fundamentals::comparator::{Comparator, Natural};
+
+/// The sorting index d-ary priority queue is an indexed min priority queue, like `IndexMinPQ`,
+/// but every node of its heap has `D` children instead of two.
+///
+/// A wider heap is shallower, so <em>insert</em> and <em>decrease_key</em> take *O*(log_D n) time,
+/// while <em>delete_min</em> looks at all `D` children on each level and takes *O*(D log_D n).
+/// On dense graphs Dijkstra decreases keys far more often than it deletes the minimum,
+/// which is where a `D` of 4 to 8 pays off. `D` must be at least 2.
+///
+/// As in `IndexMinPQ`, the order is decided by a `Comparator`, which is the natural order of the keys
+/// unless one is given with `with_comparator`.
+///
+/// # Examples
+/// ```
+/// use itualgs_rs::sorting::index_dary_pq::IndexDaryPQ;
+///
+/// let mut pq = IndexDaryPQ::<u32, 4>::new(10);
+/// pq.insert(&3, 30);
+/// pq.insert(&7, 10);
+/// pq.insert(&1, 20);
+/// pq.decrease_key(&3, 5);
+///
+/// assert_eq!(pq.key_of(&3), Some(&5));
+/// assert_eq!(pq.min_key(), Some(5));
+/// assert_eq!(pq.delete_min(), 3);
+/// assert_eq!(pq.delete_min(), 7);
+/// assert_eq!(pq.size(), 1);
+/// ```
+pub struct IndexDaryPQ<T, const D: usize, C = Natural> {
+    max_n: usize,
+    pq: Vec<usize>,
+    qp: Vec<Option<usize>>,
+    keys: Vec<Option<T>>,
+    cmp: C,
+}
+
+impl<T, const D: usize> IndexDaryPQ<T, D>
+    where T: Ord + Clone
+{
+    /// Create an empty priority queue for the indices `0..max_n`.
+    pub fn new(max_n: usize) -> Self {
+        Self::with_comparator(max_n, Natural)
+    }
+}
+
+impl<T, const D: usize, C> IndexDaryPQ<T, D, C>
+    where T: Clone, C: Comparator<T>
+{
+    /// Create an empty priority queue for the indices `0..max_n`, ordered by `cmp`.
+    pub fn with_comparator(max_n: usize, cmp: C) -> Self {
+        if D < 2 {
+            panic!("A d-ary heap needs at least two children per node!!")
+        }
+        IndexDaryPQ { max_n, pq: Vec::with_capacity(max_n), qp: vec![None; max_n], keys: vec![None; max_n], cmp }
+    }
+
+    fn validate(&self, i: &usize) {
+        if *i >= self.max_n {
+            panic!("Index out of bounds!!")
+        }
+    }
+
+    /// Returns the position of index `i` in the heap. Panics if `i` is not in the queue.
+    fn position(&self, i: &usize) -> usize {
+        self.validate(i);
+        match self.qp[*i] {
+            Some(k) => k,
+            None => panic!("Index is not in the priority queue!!"),
+        }
+    }
+
+    /// Makes room for the indices up to `new_max_n`, keeping every index and key in the queue.
+    /// Panics if `new_max_n` is smaller than the current maximum.
+    pub fn grow(&mut self, new_max_n: usize) {
+        if new_max_n < self.max_n {
+            panic!("Cannot shrink the index range!!")
+        }
+        self.max_n = new_max_n;
+        self.qp.resize(new_max_n, None);
+        self.keys.resize(new_max_n, None);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.pq.len()
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.validate(&i);
+        self.qp[i].is_some()
+    }
+
+    /// Associates `key` with index `i`. Panics if `i` is already in the queue.
+    pub fn insert(&mut self, i: &usize, key: T) {
+        self.validate(i);
+        if self.qp[*i].is_some() {
+            panic!("Index is already in the priority queue!!")
+        }
+        let k = self.pq.len();
+        self.pq.push(*i);
+        self.qp[*i] = Some(k);
+        self.keys[*i] = Some(key);
+        self.swim(k);
+    }
+
+    /// Returns the index with the smallest key. Panics if the queue is empty.
+    pub fn min_index(&self) -> usize {
+        match self.pq.first() {
+            Some(i) => *i,
+            None => panic!("The priority queue is empty!!"),
+        }
+    }
+
+    /// Returns a copy of the smallest key, or `None` if the queue is empty.
+    pub fn min_key(&self) -> Option<T> {
+        self.pq.first().and_then(|i| self.keys[*i].clone())
+    }
+
+    /// Removes the smallest key and returns its index. Panics if the queue is empty.
+    pub fn delete_min(&mut self) -> usize {
+        let min = self.min_index();
+        self.delete(&min);
+        min
+    }
+
+    /// Returns the key associated with index `i`, without cloning it.
+    pub fn key_of(&self, i: &usize) -> Option<&T> {
+        self.validate(i);
+        self.keys[*i].as_ref()
+    }
+
+    /// Changes the key of index `i`. Panics if `i` is not in the queue.
+    pub fn change_key(&mut self, i: &usize, key: T) {
+        let k = self.position(i);
+        self.keys[*i] = Some(key);
+        self.swim(k);
+        self.sink(k);
+    }
+
+    /// Lowers the key of index `i`. Panics if `i` is not in the queue.
+    pub fn decrease_key(&mut self, i: &usize, key: T) {
+        let k = self.position(i);
+        self.keys[*i] = Some(key);
+        self.swim(k);
+    }
+
+    /// Raises the key of index `i`. Panics if `i` is not in the queue.
+    pub fn increase_key(&mut self, i: &usize, key: T) {
+        let k = self.position(i);
+        self.keys[*i] = Some(key);
+        self.sink(k);
+    }
+
+    /// Removes index `i` and its key. Panics if `i` is not in the queue.
+    pub fn delete(&mut self, i: &usize) {
+        let k = self.position(i);
+        let last = self.pq.len() - 1;
+        self.exch(k, last);
+        self.pq.pop();
+        if k < self.pq.len() {
+            self.swim(k);
+            self.sink(k);
+        }
+        self.keys[*i] = None;
+        self.qp[*i] = None;
+    }
+
+    fn greater(&self, i: usize, j: usize) -> bool {
+        let a = self.keys[self.pq[i]].as_ref().unwrap();
+        let b = self.keys[self.pq[j]].as_ref().unwrap();
+        self.cmp.compare(a, b) == Ordering::Greater
+    }
+
+    fn swim(&mut self, mut k: usize) {
+        while k > 0 && self.greater((k - 1) / D, k) {
+            self.exch(k, (k - 1) / D);
+            k = (k - 1) / D;
+        }
+    }
+
+    fn sink(&mut self, mut k: usize) {
+        let n = self.pq.len();
+        while D * k + 1 < n {
+            let first = D * k + 1;
+            let mut j = first;
+            for c in first + 1..(first + D).min(n) {
+                if self.greater(j, c) {
+                    j = c;
+                }
+            }
+            if !self.greater(k, j) {
+                break;
+            }
+            self.exch(k, j);
+            k = j;
+        }
+    }
+
+    fn exch(&mut self, i: usize, j: usize) {
+        self.pq.swap(i, j);
+        self.qp[self.pq[i]] = Some(i);
+        self.qp[self.pq[j]] = Some(j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fundamentals::comparator::{Natural, Reversed}, randomization::lcg_random::Lcg};
+
+    use super::IndexDaryPQ;
+
+    fn drain<const D: usize>(seed: u64) {
+        let mut rng = Lcg::new(seed);
+        let n = 200;
+        let mut pq = IndexDaryPQ::<usize, D>::new(n / 2);
+        pq.grow(n);
+        let mut keys = vec![None; n];
+        for (i, slot) in keys.iter_mut().enumerate() {
+            let key = rng.uniform(1000);
+            pq.insert(&i, key);
+            *slot = Some(key);
+        }
+        for i in (0..n).step_by(3) {
+            let key = rng.uniform(1000);
+            pq.change_key(&i, key);
+            keys[i] = Some(key);
+        }
+        for i in (0..n).step_by(7) {
+            pq.delete(&i);
+            keys[i] = None;
+        }
+        let mut last = 0;
+        while !pq.is_empty() {
+            let key = pq.min_key().unwrap();
+            let i = pq.delete_min();
+            assert_eq!(keys[i].take(), Some(key));
+            assert!(key >= last);
+            last = key;
+        }
+        assert!(keys.iter().all(|k| k.is_none()));
+    }
+
+    #[test]
+    fn test_drain_in_order() {
+        drain::<2>(1);
+        drain::<3>(2);
+        drain::<4>(3);
+        drain::<8>(4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unary_heap() {
+        IndexDaryPQ::<u32, 1>::new(4);
+    }
+
+    #[test]
+    fn test_comparator() {
+        let mut pq = IndexDaryPQ::<u32, 3, _>::with_comparator(6, Reversed(Natural));
+        for (i, key) in [40, 10, 60, 30, 50, 20].iter().enumerate() {
+            pq.insert(&i, *key);
+        }
+        assert_eq!(pq.min_key(), Some(60));
+        let order: Vec<usize> = (0..6).map(|_| pq.delete_min()).collect();
+        assert_eq!(order, vec![2, 4, 0, 3, 5, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The priority queue is empty!!")]
+    fn test_delete_min_on_empty_queue() {
+        let mut pq = IndexDaryPQ::<u32, 4>::new(4);
+        assert_eq!(pq.min_key(), None);
+        pq.delete_min();
+    }
+
+    #[test]
+    #[should_panic(expected = "Index is not in the priority queue!!")]
+    fn test_change_key_of_missing_index() {
+        let mut pq = IndexDaryPQ::<u32, 4>::new(4);
+        pq.insert(&0, 1);
+        pq.decrease_key(&1, 0);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::fundamentals::comparator::{Comparator, Natural, Reversed};
+
+use super::index_min_pq::IndexMinPQ;
+
+/// The sorting index max priority queue associates a key with each of the indices 0 to max_n - 1,
+/// and supports removing the index with the largest key and changing the key of any index.
+///
+/// It is an `IndexMinPQ` with the comparator turned around, so it has the same running times:
+/// insert, delete_max, delete and the key changes take *O*(log n), the other operations constant time.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::sorting::index_max_pq::IndexMaxPQ;
+///
+/// let mut pq = IndexMaxPQ::new(4);
+/// pq.insert(&0, 30);
+/// pq.insert(&3, 10);
+/// pq.insert(&1, 20);
+/// pq.increase_key(&3, 40);
+/// assert_eq!(pq.delete_max(), 3);
+/// assert_eq!(pq.max_key(), Some(30));
+/// ```
+pub struct IndexMaxPQ<T, C = Natural> {
+    heap: IndexMinPQ<T, Reversed<C>>,
+}
+
+impl<T> IndexMaxPQ<T>
+    where T:Ord, T:Clone
+{
+    pub fn new(max_n: usize) -> Self {
+        Self::with_comparator(max_n, Natural)
+    }
+}
+
+impl<T, C> IndexMaxPQ<T, C>
+    where T:Clone, C:Comparator<T>
+{
+    /// Creates an empty queue for the indices 0 to max_n - 1, with the largest key according to `cmp` first.
+    pub fn with_comparator(max_n: usize, cmp: C) -> Self {
+        IndexMaxPQ { heap: IndexMinPQ::with_comparator(max_n, Reversed(cmp)) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.heap.contains(i)
+    }
+
+    pub fn size(&self) -> usize {
+        self.heap.size()
+    }
+
+    pub fn insert(&mut self, i: &usize, key: T) {
+        self.heap.insert(i, key)
+    }
+
+    pub fn max_index(&self) -> usize {
+        self.heap.min_index()
+    }
+
+    pub fn max_key(&self) -> Option<T> {
+        self.heap.min_key()
+    }
+
+    pub fn delete_max(&mut self) -> usize {
+        self.heap.delete_min()
+    }
+
+    /// Returns the key associated with index `i`, without cloning it.
+    pub fn key_of(&self, i: &usize) -> Option<&T> {
+        self.heap.key_of(i)
+    }
+
+    /// Makes room for the indices up to `new_max_n`, keeping every index and key in the queue.
+    /// Panics if `new_max_n` is smaller than the current maximum.
+    pub fn grow(&mut self, new_max_n: usize) {
+        self.heap.grow(new_max_n)
+    }
+
+    pub fn change_key(&mut self, i: &usize, key: T) {
+        self.heap.change_key(i, key)
+    }
+
+    /// A larger key moves the index towards the front, which is a smaller key for the reversed heap
+    pub fn increase_key(&mut self, i: &usize, key: T) {
+        self.heap.decrease_key(i, key)
+    }
+
+    pub fn decrease_key(&mut self, i: &usize, key: T) {
+        self.heap.increase_key(i, key)
+    }
+
+    pub fn delete(&mut self, i: &usize) {
+        self.heap.delete(i)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::IndexMaxPQ;
+
+    #[test]
+    fn test_delete_max_in_order(){
+        let mut pq = IndexMaxPQ::<u32>::new(8);
+        for (i, key) in [50, 20, 70, 10, 40, 60, 30, 80].iter().enumerate() {
+            pq.insert(&i, *key);
+        }
+        pq.increase_key(&3, 75);
+        pq.decrease_key(&7, 5);
+        pq.delete(&2);
+        assert_eq!(pq.key_of(&3), Some(&75));
+        assert_eq!(pq.max_key(), Some(75));
+
+        let mut order = Vec::new();
+        while !pq.is_empty() {
+            order.push(pq.delete_max());
+        }
+        assert_eq!(order, vec![3, 5, 0, 4, 6, 1, 7]);
+    }
+
+    #[test]
+    fn test_grow(){
+        let mut pq = IndexMaxPQ::<u32>::new(1);
+        pq.insert(&0, 10);
+        pq.grow(5);
+        pq.insert(&4, 20);
+        assert_eq!(pq.size(), 2);
+        assert_eq!(pq.delete_max(), 4);
+        assert_eq!(pq.delete_max(), 0);
+    }
+}
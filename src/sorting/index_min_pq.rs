@@ -1,13 +1,35 @@
 use std::cmp::Ordering;
 
+use crate::fundamentals::comparator::{Comparator, Natural};
 
-
-pub struct IndexMinPQ<T> {
+/// The sorting index min priority queue associates a key with each of the indices 0 to max_n - 1,
+/// and supports removing the index with the smallest key and changing the key of any index.
+///
+/// The order is decided by a `Comparator`, which is the natural order of the keys unless one is given
+/// with `with_comparator`. `IndexMaxPQ` is this queue with the comparator turned around.
+///
+/// Insert, delete_min, delete and the key changes take *O*(log n), the other operations constant time.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::sorting::index_min_pq::IndexMinPQ;
+///
+/// let mut pq = IndexMinPQ::new(4);
+/// pq.insert(&0, 30);
+/// pq.insert(&3, 10);
+/// pq.insert(&1, 20);
+/// pq.decrease_key(&0, 5);
+/// assert_eq!(pq.delete_min(), 0);
+/// assert_eq!(pq.min_key(), Some(10));
+/// ```
+pub struct IndexMinPQ<T, C = Natural> {
     max_n: usize,
     n: usize,
     pq: Vec<usize>,
     qp: Vec<Option<usize>>,
     keys: Vec<Option<T>>,
+    cmp: C,
 }
 
 
@@ -16,12 +38,23 @@ impl<T> IndexMinPQ<T>
     {
     
     pub fn new(max_n: usize) -> Self {
+        Self::with_comparator(max_n, Natural)
+    }
+}
+
+impl<T, C> IndexMinPQ<T, C>
+    where T:Clone, C:Comparator<T>
+    {
+
+    /// Creates an empty queue for the indices 0 to max_n - 1, ordered by `cmp`.
+    pub fn with_comparator(max_n: usize, cmp: C) -> Self {
         IndexMinPQ { 
-            max_n: max_n, 
+            max_n, 
             n: 0, 
             pq: vec![0;max_n+1], 
             qp: vec![None;max_n+1], 
-            keys: vec![None; max_n+1] }
+            keys: vec![None; max_n+1],
+            cmp }
     }
 
     pub fn is_empty(&self) -> bool{
@@ -58,7 +91,7 @@ impl<T> IndexMinPQ<T>
     }
     
     pub fn min_key(&self) -> Option<T> {
-        if let Some(e) = &self.keys[1] {
+        if let Some(e) = &self.keys[self.pq[1]] {
             return Some(e.clone());
         }
         None
@@ -66,22 +99,31 @@ impl<T> IndexMinPQ<T>
 
     pub fn delete_min(&mut self) -> usize {
         let min = self.min_index();
-        let mut n = self.n;
+        let n = self.n;
         self.exch(&1, &n);
-        n -= 1;
+        self.n -= 1;
         self.sink(&1);
         self.qp[min] = None;
         self.keys[min] = None;
-        self.n = n;
         min
     }
 
-    pub fn key_of(&self, i: &usize) -> Option<T> {
+    /// Returns the key associated with index `i`, without cloning it.
+    pub fn key_of(&self, i: &usize) -> Option<&T> {
         self.validate(i);
-        if let Some(e) = &self.keys[*i] {
-            return Some(e.clone());
+        self.keys[*i].as_ref()
+    }
+
+    /// Makes room for the indices up to `new_max_n`, keeping every index and key in the queue.
+    /// Panics if `new_max_n` is smaller than the current maximum.
+    pub fn grow(&mut self, new_max_n: usize) {
+        if new_max_n < self.max_n {
+            panic!("Cannot shrink the index range!!")
         }
-        None
+        self.max_n = new_max_n;
+        self.pq.resize(new_max_n+1, 0);
+        self.qp.resize(new_max_n+1, None);
+        self.keys.resize(new_max_n+1, None);
     }
 
     pub fn change_key(&mut self, i:&usize, key:T){
@@ -108,25 +150,25 @@ impl<T> IndexMinPQ<T>
         let tmp = self.n;
         self.exch(&index, &tmp);
         self.n -= 1;
-        self.swim(&index);
-        self.sink(&index);
+        if index <= self.n {
+            self.swim(&index);
+            self.sink(&index);
+        }
         self.keys[*i] = None;
         self.qp[*i] = None;
     }
 
     fn greater(&self, i:&usize, j:&usize) -> bool {
-        self.keys[
-            self.pq[*i]]
-            .cmp(
-            &self.keys[
-            self.pq[*j]]) == Ordering::Greater
+        let a = self.keys[self.pq[*i]].as_ref().unwrap();
+        let b = self.keys[self.pq[*j]].as_ref().unwrap();
+        self.cmp.compare(a, b) == Ordering::Greater
     }
 
     fn swim(&mut self, k:&usize){
         let mut tmp = *k;
         while tmp > 1 && self.greater(&(tmp/2), &tmp) {
-            self.exch(k, &(tmp/2));
-            tmp = tmp/2;
+            self.exch(&tmp, &(tmp/2));
+            tmp /= 2;
         }
     }
 
@@ -147,8 +189,8 @@ impl<T> IndexMinPQ<T>
 
     fn exch(&mut self, i:&usize, j:&usize){
         self.pq.swap(*i, *j);
-        self.qp.get(self.pq[*i]).replace(&Some(*i));
-        self.qp.get(self.pq[*j]).replace(&Some(*j));
+        self.qp[self.pq[*i]] = Some(*i);
+        self.qp[self.pq[*j]] = Some(*j);
     }
 }
 
@@ -164,4 +206,95 @@ mod tests {
         pq.insert(&900, "hej");
         pq.insert(&7, "hej");
     }
+
+    #[test]
+    fn test_delete_min_in_order(){
+        let mut pq = IndexMinPQ::<u32>::new(8);
+        for (i, key) in [50, 20, 70, 10, 40, 60, 30, 80].iter().enumerate() {
+            pq.insert(&i, *key);
+        }
+        pq.decrease_key(&7, 5);
+        pq.increase_key(&3, 65);
+        pq.delete(&2);
+        assert_eq!(pq.key_of(&7), Some(&5));
+        assert_eq!(pq.min_key(), Some(5));
+
+        let mut order = Vec::new();
+        while !pq.is_empty() {
+            order.push(pq.delete_min());
+        }
+        assert_eq!(order, vec![7, 1, 6, 4, 0, 5, 3]);
+        assert_eq!(pq.key_of(&7), None);
+    }
+
+    #[test]
+    fn test_grow(){
+        let mut pq = IndexMinPQ::<u32>::new(2);
+        pq.insert(&0, 30);
+        pq.insert(&1, 10);
+        pq.grow(10);
+        pq.insert(&9, 20);
+        assert!(pq.contains(0));
+        assert_eq!(pq.delete_min(), 1);
+        assert_eq!(pq.delete_min(), 9);
+        assert_eq!(pq.delete_min(), 0);
+    }
+
+    #[test]
+    fn test_swim_moves_the_key_more_than_one_level(){
+        // Every insert is a new minimum that has to swim all the way to the root
+        let mut pq = IndexMinPQ::<u32>::new(8);
+        for i in 0..8 {
+            pq.insert(&i, 100 - i as u32);
+        }
+        assert_eq!(pq.min_index(), 7);
+        let order: Vec<usize> = (0..8).map(|_| pq.delete_min()).collect();
+        assert_eq!(order, vec![7, 6, 5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_exch_keeps_positions_up_to_date(){
+        let mut pq = IndexMinPQ::<u32>::new(4);
+        pq.insert(&0, 10);
+        // Swims above index 0, which moves index 0 down to position 2
+        pq.insert(&1, 5);
+        pq.decrease_key(&0, 1);
+        assert_eq!(pq.min_index(), 0);
+    }
+
+    #[test]
+    fn test_min_key_of_any_index(){
+        let mut pq = IndexMinPQ::<u32>::new(4);
+        pq.insert(&3, 7);
+        assert_eq!(pq.min_key(), Some(7));
+    }
+
+    #[test]
+    fn test_delete_min_does_not_sink_into_the_removed_slot(){
+        let mut pq = IndexMinPQ::<u32>::new(4);
+        pq.insert(&0, 1);
+        pq.insert(&1, 3);
+        pq.insert(&2, 2);
+        assert_eq!(pq.delete_min(), 0);
+        assert_eq!(pq.delete_min(), 2);
+        assert_eq!(pq.delete_min(), 1);
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_comparator(){
+        let mut pq = IndexMinPQ::with_comparator(3, |a: &&str, b: &&str| a.len().cmp(&b.len()));
+        pq.insert(&0, "ferris");
+        pq.insert(&1, "crab");
+        pq.insert(&2, "rustacean");
+        assert_eq!(pq.delete_min(), 1);
+        assert_eq!(pq.delete_min(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds(){
+        let mut pq = IndexMinPQ::<u32>::new(2);
+        pq.insert(&2, 1);
+    }
 }
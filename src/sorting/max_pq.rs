@@ -0,0 +1,168 @@
+use crate::fundamentals::comparator::{Comparator, Natural, Reversed};
+
+use super::min_pq::{self, MinPQ};
+
+/// The sorting max priority queue module represents a priority queue of generic keys.
+/// It supports the usual <em>push</em> and <em>pop</em> operations, along with methods for
+/// peeking at the largest key, testing if the priority queue is empty, and iterating through the keys.
+///
+/// It is a `MinPQ` with the comparator turned around, so it has the same running times:
+/// push and pop take *O*(log n), `from_vec` takes *O*(n), and iterating goes through the keys
+/// in descending order without changing the heap.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::sorting::max_pq::MaxPQ;
+///
+/// let mut pq = MaxPQ::from_vec(vec![5, 1, 4]);
+/// pq.push(3);
+/// assert_eq!(pq.peek(), Some(&5));
+/// assert_eq!(pq.iter().collect::<Vec<_>>(), vec![&5, &4, &3, &1]);
+///
+/// assert_eq!(pq.pop(), Some(5));
+/// assert_eq!(pq.size(), 3);
+/// ```
+pub struct MaxPQ<T, C = Natural> {
+    heap: MinPQ<T, Reversed<C>>,
+}
+
+impl<T: Ord> MaxPQ<T> {
+    /// Create a new empty priority queue.
+    pub fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+
+    /// Create a new empty priority queue with room for `capacity` keys before it resizes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        MaxPQ { heap: MinPQ::from_vec_with_comparator(Vec::with_capacity(capacity), Reversed(Natural)) }
+    }
+
+    /// Builds a priority queue from the keys in linear time.
+    pub fn from_vec(keys: Vec<T>) -> Self {
+        Self::from_vec_with_comparator(keys, Natural)
+    }
+}
+
+impl<T: Ord> Default for MaxPQ<T> {
+    fn default() -> Self {
+        MaxPQ::new()
+    }
+}
+
+impl<T, C: Comparator<T>> MaxPQ<T, C> {
+    /// Create a new empty priority queue ordered by `cmp`, the largest key comes first.
+    pub fn with_comparator(cmp: C) -> Self {
+        MaxPQ { heap: MinPQ::with_comparator(Reversed(cmp)) }
+    }
+
+    /// Builds a priority queue ordered by `cmp` from the keys in linear time.
+    pub fn from_vec_with_comparator(keys: Vec<T>, cmp: C) -> Self {
+        MaxPQ { heap: MinPQ::from_vec_with_comparator(keys, Reversed(cmp)) }
+    }
+
+    /// Adds a key to the priority queue.
+    pub fn push(&mut self, key: T) {
+        self.heap.push(key)
+    }
+
+    /// Removes and returns the largest key.
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    /// Returns an iterator over the keys in descending order, leaving the heap as it is.
+    pub fn iter(&self) -> Iter<'_, T, C> {
+        Iter { inner: self.heap.iter() }
+    }
+}
+
+impl<T, C> MaxPQ<T, C> {
+    /// Returns the largest key.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Checks if the priority queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the number of keys in the priority queue.
+    pub fn size(&self) -> usize {
+        self.heap.size()
+    }
+}
+
+/// An iterator over the keys of a `MaxPQ` in descending order, created by `MaxPQ::iter`.
+pub struct Iter<'a, T, C = Natural> {
+    inner: min_pq::Iter<'a, T, Reversed<C>>,
+}
+
+impl<'a, T, C: Comparator<T>> Iterator for Iter<'a, T, C> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+}
+
+impl<'a, T, C: Comparator<T>> IntoIterator for &'a MaxPQ<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, C>;
+
+    fn into_iter(self) -> Iter<'a, T, C> {
+        self.iter()
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for MaxPQ<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.heap.extend(iter)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MaxPQ<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        MaxPQ::from_vec(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::randomization::lcg_random::Lcg;
+
+    use super::MaxPQ;
+
+    #[test]
+    fn test_pop_and_iter_in_order() {
+        let mut rng = Lcg::new(5);
+        let keys: Vec<usize> = (0..400).map(|_| rng.uniform(50)).collect();
+        let mut sorted = keys.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+
+        let mut pq: MaxPQ<usize> = keys.into_iter().collect();
+        assert_eq!(pq.iter().copied().collect::<Vec<_>>(), sorted);
+        for expected in sorted {
+            assert_eq!(pq.pop(), Some(expected));
+        }
+        assert_eq!(pq.pop(), None);
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut pq = MaxPQ::with_capacity(3);
+        assert!(pq.is_empty());
+        pq.extend([2, 9, 4, 7]);
+        assert_eq!(pq.pop(), Some(9));
+        assert_eq!(pq.peek(), Some(&7));
+    }
+
+    #[test]
+    fn test_comparator() {
+        let mut pq = MaxPQ::with_comparator(|a: &&str, b: &&str| a.len().cmp(&b.len()));
+        pq.extend(["fig", "banana", "kiwi"]);
+        assert_eq!(pq.peek(), Some(&"banana"));
+        assert_eq!(pq.iter().copied().collect::<Vec<_>>(), vec!["banana", "kiwi", "fig"]);
+    }
+}
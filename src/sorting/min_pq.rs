@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+
+use crate::fundamentals::comparator::{Comparator, Natural};
+
+/// The sorting min priority queue module represents a priority queue of generic keys.
+/// It supports the usual <em>push</em> and <em>pop</em> operations, along with methods for
+/// peeking at the smallest key, testing if the priority queue is empty, and iterating through the keys.
+///
+/// This implementation uses a binary heap stored in a Vec\<T\>, which resizes automatically.
+/// Push and pop take logarithmic amortized time: *O*(log n). Peek, size and is_empty take constant time.
+/// `from_vec` builds the heap bottom-up in linear time: *O*(n).
+///
+/// Iterating goes through the keys in ascending order without changing the heap, using a second small
+/// heap of positions: taking k keys costs *O*(k log k).
+///
+/// The order is decided by a `Comparator`, which is the natural order of the keys unless one is given
+/// with `with_comparator`.
+///
+/// # Examples
+///
+/// ```
+/// use itualgs_rs::sorting::min_pq::MinPQ;
+///
+/// let mut pq = MinPQ::from_vec(vec![5, 1, 4]);
+/// pq.push(3);
+/// assert_eq!(pq.peek(), Some(&1));
+/// assert_eq!(pq.iter().collect::<Vec<_>>(), vec![&1, &3, &4, &5]);
+///
+/// assert_eq!(pq.pop(), Some(1));
+/// assert_eq!(pq.size(), 3);
+///
+/// // Shortest word first, ties broken alphabetically
+/// let mut words = MinPQ::with_comparator(|a: &&str, b: &&str| a.len().cmp(&b.len()).then(a.cmp(b)));
+/// words.extend(["pear", "fig", "apple", "kiwi"]);
+/// assert_eq!(words.pop(), Some("fig"));
+/// assert_eq!(words.pop(), Some("kiwi"));
+/// ```
+pub struct MinPQ<T, C = Natural> {
+    pq: Vec<T>,
+    cmp: C,
+}
+
+impl<T: Ord> MinPQ<T> {
+    /// Create a new empty priority queue.
+    pub fn new() -> Self {
+        MinPQ { pq: Vec::new(), cmp: Natural }
+    }
+
+    /// Create a new empty priority queue with room for `capacity` keys before it resizes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        MinPQ { pq: Vec::with_capacity(capacity), cmp: Natural }
+    }
+
+    /// Builds a priority queue from the keys in linear time.
+    pub fn from_vec(keys: Vec<T>) -> Self {
+        Self::from_vec_with_comparator(keys, Natural)
+    }
+}
+
+impl<T: Ord> Default for MinPQ<T> {
+    fn default() -> Self {
+        MinPQ::new()
+    }
+}
+
+impl<T, C: Comparator<T>> MinPQ<T, C> {
+    /// Create a new empty priority queue ordered by `cmp`.
+    pub fn with_comparator(cmp: C) -> Self {
+        MinPQ { pq: Vec::new(), cmp }
+    }
+
+    /// Builds a priority queue ordered by `cmp` from the keys in linear time.
+    pub fn from_vec_with_comparator(keys: Vec<T>, cmp: C) -> Self {
+        let mut pq = MinPQ { pq: keys, cmp };
+        let n = pq.pq.len();
+        for k in (0..n / 2).rev() {
+            sink(&mut pq.pq, k, |a, b| pq.cmp.compare(a, b) == Ordering::Greater);
+        }
+        pq
+    }
+
+    /// Adds a key to the priority queue.
+    pub fn push(&mut self, key: T) {
+        self.pq.push(key);
+        let n = self.pq.len();
+        let cmp = &self.cmp;
+        swim(&mut self.pq, n - 1, |a, b| cmp.compare(a, b) == Ordering::Greater);
+    }
+
+    /// Removes and returns the smallest key.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.pq.is_empty() {
+            return None;
+        }
+        let min = self.pq.swap_remove(0);
+        let cmp = &self.cmp;
+        sink(&mut self.pq, 0, |a, b| cmp.compare(a, b) == Ordering::Greater);
+        Some(min)
+    }
+
+    /// Returns an iterator over the keys in ascending order, leaving the heap as it is.
+    pub fn iter(&self) -> Iter<'_, T, C> {
+        let mut next = Vec::new();
+        if !self.pq.is_empty() {
+            next.push(0);
+        }
+        Iter { pq: &self.pq, cmp: &self.cmp, next }
+    }
+}
+
+impl<T, C> MinPQ<T, C> {
+    /// Returns the smallest key.
+    pub fn peek(&self) -> Option<&T> {
+        self.pq.first()
+    }
+
+    /// Checks if the priority queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
+
+    /// Returns the number of keys in the priority queue.
+    pub fn size(&self) -> usize {
+        self.pq.len()
+    }
+}
+
+/// Moves the key at `k` up until its parent is not greater.
+fn swim<T>(pq: &mut [T], mut k: usize, greater: impl Fn(&T, &T) -> bool) {
+    while k > 0 && greater(&pq[(k - 1) / 2], &pq[k]) {
+        pq.swap((k - 1) / 2, k);
+        k = (k - 1) / 2;
+    }
+}
+
+/// Moves the key at `k` down until none of its children are smaller.
+fn sink<T>(pq: &mut [T], mut k: usize, greater: impl Fn(&T, &T) -> bool) {
+    let n = pq.len();
+    while 2 * k + 1 < n {
+        let mut j = 2 * k + 1;
+        if j + 1 < n && greater(&pq[j], &pq[j + 1]) {
+            j += 1;
+        }
+        if !greater(&pq[k], &pq[j]) {
+            break;
+        }
+        pq.swap(k, j);
+        k = j;
+    }
+}
+
+/// An iterator over the keys of a `MinPQ` in ascending order, created by `MinPQ::iter`.
+///
+/// It keeps a heap of positions in the priority queue whose parents have been returned,
+/// so the next key is always at its top.
+pub struct Iter<'a, T, C = Natural> {
+    pq: &'a [T],
+    cmp: &'a C,
+    next: Vec<usize>,
+}
+
+impl<'a, T, C: Comparator<T>> Iterator for Iter<'a, T, C> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.next.is_empty() {
+            return None;
+        }
+        let (pq, cmp) = (self.pq, self.cmp);
+        let greater = |i: &usize, j: &usize| cmp.compare(&pq[*i], &pq[*j]) == Ordering::Greater;
+        let k = self.next.swap_remove(0);
+        sink(&mut self.next, 0, greater);
+        for child in [2 * k + 1, 2 * k + 2] {
+            if child < pq.len() {
+                self.next.push(child);
+                let last = self.next.len() - 1;
+                swim(&mut self.next, last, greater);
+            }
+        }
+        Some(&pq[k])
+    }
+}
+
+impl<'a, T, C: Comparator<T>> IntoIterator for &'a MinPQ<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, C>;
+
+    fn into_iter(self) -> Iter<'a, T, C> {
+        self.iter()
+    }
+}
+
+impl<T, C: Comparator<T>> Extend<T> for MinPQ<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for key in iter {
+            self.push(key);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinPQ<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        MinPQ::from_vec(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::randomization::lcg_random::Lcg;
+
+    use super::MinPQ;
+
+    #[test]
+    fn test_pop_in_order() {
+        let mut rng = Lcg::new(7);
+        let keys: Vec<usize> = (0..500).map(|_| rng.uniform(100)).collect();
+        let mut pq = MinPQ::new();
+        pq.extend(keys.iter().copied());
+        let mut sorted = keys.clone();
+        sorted.sort();
+
+        let mut popped = Vec::new();
+        while let Some(key) = pq.pop() {
+            popped.push(key);
+        }
+        assert_eq!(popped, sorted);
+        assert_eq!(pq.peek(), None);
+    }
+
+    #[test]
+    fn test_from_vec_and_iter_leave_heap() {
+        let mut rng = Lcg::new(11);
+        let keys: Vec<usize> = (0..300).map(|_| rng.uniform(1000)).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+
+        let mut pq = MinPQ::from_vec(keys);
+        assert_eq!(pq.iter().copied().collect::<Vec<_>>(), sorted);
+        assert_eq!(pq.size(), 300);
+        assert_eq!(pq.iter().take(3).count(), 3);
+        for expected in sorted {
+            assert_eq!(pq.pop(), Some(expected));
+        }
+        assert!(pq.is_empty());
+        assert_eq!(pq.iter().next(), None);
+    }
+
+    #[test]
+    fn test_comparator() {
+        let mut pq = MinPQ::from_vec_with_comparator(vec![(1, 'a'), (3, 'b'), (2, 'c')], |a: &(i32, char), b: &(i32, char)| b.0.cmp(&a.0));
+        pq.push((5, 'd'));
+        assert_eq!(pq.iter().map(|p| p.1).collect::<String>(), "dbca");
+        assert_eq!(pq.pop(), Some((5, 'd')));
+    }
+}
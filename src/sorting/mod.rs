@@ -3,3 +3,7 @@ pub mod selection;
 pub mod merge;
 pub mod quick;
 pub mod index_min_pq;
+pub mod index_max_pq;
+pub mod index_dary_pq;
+pub mod min_pq;
+pub mod max_pq;